@@ -1,113 +1,512 @@
 use crate::{
-    constants::{API_BASE_MAINNET, API_BASE_TESTNET, PROTOCOL_VERSION},
+    constants::{API_BASE, PATH_PREFIX, PROTOCOL_VERSION},
+    rate_limit::{RateLimit, RateLimiter},
+    retry::{is_retryable_error, is_retryable_status, retry_after, RetryPolicy},
+    stream::{paginate, Page, PageRequest, StreamOptions},
     types::{
         api::{
-            AsksRequest, AsksResponse, BuyTokensRequest, BuyTokensResponse, MagicedenBuyTokensErrorResponse, MagicedenErrorParseResponse,
-            MagicedenOrderAlreadyFilledError, ServerError,
+            Activity, ActivityResponse, AsksRequest, AsksResponse, AttributesAllResponse, BidRequest, BidsRequest, BidsResponse,
+            BuyTokenCheck, BuyTokenError, BuyTokensRequest, BuyTokensResponse, CancelOrdersResponse, CancelRequest,
+            CollectionActivityRequest, CollectionAttributes, CollectionDetails, CollectionsRequest, CollectionsResponse,
+            ExploreAttributesRequest, ExploreAttributesResponse, HolderConcentration, ListTokensRequest, ListTokensResponse,
+            MagicedenErrorParseResponse, Method, MintError, MintRequest, MintTokensResponse, Order, OrderDepthRequest, OrderDepthResponse,
+            OwnersRequest, OwnersResponse, PlaceBidsResponse, PostData, SalesRequest, SalesResponse, SellTokensRequest, SellTokensResponse,
+            StepStatus, TokensRequest, TokensResponse, UserActivityRequest, UserTokensRequest, UserTokensResponse,
         },
         ApiUrl, Chain, MagicedenApiError,
     },
 };
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use reqwest::{
     header::{self, HeaderMap},
-    Client, ClientBuilder, StatusCode,
+    Client, ClientBuilder, RequestBuilder, Response,
 };
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::time::Instant;
+
+// Max page size and offset of the owners endpoint
+const OWNERS_PAGE_SIZE: u16 = 500;
+const MAX_OWNERS_OFFSET: u32 = 10_000;
 
 #[derive(Debug, Clone)]
 pub struct MagicedenClient {
     client: Client,
     chain: Chain,
     url: ApiUrl,
+    retry: Option<RetryPolicy>,
+    limiter: Option<Arc<RateLimiter>>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct MagicedenApiConfig {
     pub api_key: Option<String>,
     pub chain: Chain,
+    // Retry policy for rate limited or failed requests. No retries if not set.
+    pub retry: Option<RetryPolicy>,
+    // Client-side rate limit shared by all endpoints and clones of the client. Not limited if not set.
+    pub rate_limit: Option<RateLimit>,
+    // Override the API base url, e.g. to use a mock server or proxy. Defaults to the Magic Eden API.
+    pub base_url: Option<String>,
+    // Override the protocol version appended to the base url. Defaults to v3, empty to omit it.
+    pub protocol_version: Option<String>,
+    // Override the path prefix of the chain specific endpoints. `{chain}` is replaced by the chain name. Defaults to rtp/{chain}.
+    pub path_prefix: Option<String>,
 }
 
 impl MagicedenClient {
     /// Create a new client with the given configuration.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid. Use [`MagicedenClient::try_new`] to handle the error instead.
     pub fn new(cfg: MagicedenApiConfig) -> Self {
+        Self::try_new(cfg).expect("invalid client configuration")
+    }
+
+    /// Create a new client with the given configuration. Fails if the api key is not a valid header value or the
+    /// http client cannot be initialized.
+    pub fn try_new(cfg: MagicedenApiConfig) -> Result<Self, MagicedenApiError> {
         let mut builder = ClientBuilder::new();
         let mut headers = HeaderMap::new();
 
         if let Some(ref api_key) = cfg.api_key {
-            headers.insert("Authorization", header::HeaderValue::from_str(format!("Bearer {}", api_key).as_str()).unwrap());
+            let value = header::HeaderValue::from_str(format!("Bearer {}", api_key).as_str())
+                .map_err(|e| MagicedenApiError::Config(format!("invalid api key: {e}")))?;
+            headers.insert("Authorization", value);
         }
 
         builder = builder.default_headers(headers);
-        let client = builder.build().unwrap();
+        let client = builder.build().map_err(|e| MagicedenApiError::Config(format!("failed to build http client: {e}")))?;
+
+        let url = ApiUrl::new(
+            cfg.base_url.as_deref().unwrap_or(API_BASE),
+            cfg.protocol_version.as_deref().unwrap_or(PROTOCOL_VERSION),
+            cfg.path_prefix.as_deref().unwrap_or(PATH_PREFIX),
+        );
+
+        let limiter = cfg.rate_limit.as_ref().map(|limit| Arc::new(RateLimiter::new(limit)));
+
+        Ok(Self { client, chain: cfg.chain, url, retry: cfg.retry, limiter })
+    }
 
-        let base_url = if cfg.chain.is_test_chain() { API_BASE_TESTNET } else { API_BASE_MAINNET };
+    /// Wait for the rate limiter, if configured.
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+    }
 
-        let base_url = format!("{base_url}/{PROTOCOL_VERSION}");
+    /// Stream the items of a paginated endpoint. `fetch` requests one page, `page` extracts its items and continuation.
+    fn stream_pages<P, R, T, F, Fut>(
+        &self,
+        mut params: P,
+        options: StreamOptions,
+        fetch: F,
+        page: fn(R) -> Page<T>,
+    ) -> BoxStream<'static, Result<T, MagicedenApiError>>
+    where
+        P: PageRequest,
+        R: 'static,
+        T: Send + 'static,
+        F: Fn(MagicedenClient, P) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<R, MagicedenApiError>> + Send + 'static,
+    {
+        if let Some(page_size) = options.page_size {
+            params.set_limit(page_size);
+        }
+        let client = self.clone();
+        let continuation = params.continuation_mut().take();
 
-        Self { client, chain: cfg.chain, url: ApiUrl { base: base_url } }
+        paginate(continuation, options.max_items, move |continuation| {
+            let mut params = params.clone();
+            *params.continuation_mut() = continuation;
+            let res = fetch(client.clone(), params);
+            async move { res.await.map(page) }
+        })
+        .boxed()
+    }
+
+    /// Send a request and retry it according to the retry policy. Non-idempotent requests are only retried if the
+    /// policy explicitly allows it.
+    async fn send(&self, request: RequestBuilder, idempotent: bool) -> Result<Response, MagicedenApiError> {
+        let Some(policy) = &self.retry else {
+            self.throttle().await;
+            return Ok(request.send().await?);
+        };
+
+        let mut attempt = 1;
+        loop {
+            self.throttle().await;
+            let Some(req) = request.try_clone() else {
+                return Ok(request.send().await?);
+            };
+            let retryable = policy.allows_retry(attempt, idempotent);
+            let res = req.send().await;
+            let delay = match &res {
+                Ok(res) if retryable && is_retryable_status(res.status()) => policy.delay(attempt, Some(res.headers())),
+                Err(e) if retryable && is_retryable_error(e) => policy.delay(attempt, None),
+                _ => None,
+            };
+            // Not retryable, or the API asks to wait longer than the policy allows
+            let Some(delay) = delay else {
+                return Ok(res?);
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
     }
 
     pub async fn retrieve_asks(&self, params: AsksRequest) -> Result<AsksResponse, MagicedenApiError> {
-        let query_parameters = serde_url_params::to_string(&params).unwrap();
-        let res = self.client.get(self.url.retrieve_asks(&self.chain, query_parameters)).send().await;
-        match res {
-            Ok(res) => {
-                let status_code = res.status();
-                let body = res.text().await?;
-                let res = serde_json::from_str::<AsksResponse>(&body);
-                match res {
-                    Ok(r) => Ok(r),
-                    Err(e) => {
-                        let e = MagicedenErrorParseResponse { body, status_code: status_code.as_u16(), error: e.to_string() };
-                        Err(MagicedenApiError::ResponseParseError(e))
-                    }
-                }
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_asks(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Stream all asks matching `params`, following the continuation until the API is exhausted.
+    pub fn asks_stream(&self, params: AsksRequest) -> BoxStream<'static, Result<Order, MagicedenApiError>> {
+        self.asks_stream_with_options(params, StreamOptions::default())
+    }
+
+    /// Stream asks matching `params` with an optional item cap and page size.
+    pub fn asks_stream_with_options(
+        &self,
+        params: AsksRequest,
+        options: StreamOptions,
+    ) -> BoxStream<'static, Result<Order, MagicedenApiError>> {
+        self.stream_pages(
+            params,
+            options,
+            |client, params| async move { client.retrieve_asks(params).await },
+            |res| Page { items: res.orders, continuation: res.continuation },
+        )
+    }
+
+    pub async fn retrieve_bids(&self, params: BidsRequest) -> Result<BidsResponse, MagicedenApiError> {
+        let query_parameters = params.to_query_string()?;
+        let res = self.send(self.client.get(self.url.retrieve_bids(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Stream all bids matching `params`, following the continuation until the API is exhausted.
+    pub fn bids_stream(&self, params: BidsRequest) -> BoxStream<'static, Result<Order, MagicedenApiError>> {
+        self.bids_stream_with_options(params, StreamOptions::default())
+    }
+
+    /// Stream bids matching `params` with an optional item cap and page size.
+    pub fn bids_stream_with_options(
+        &self,
+        params: BidsRequest,
+        options: StreamOptions,
+    ) -> BoxStream<'static, Result<Order, MagicedenApiError>> {
+        self.stream_pages(
+            params,
+            options,
+            |client, params| async move { client.retrieve_bids(params).await },
+            |res| Page { items: res.orders, continuation: res.continuation },
+        )
+    }
+
+    /// Retrieve tokens with their metadata, floor ask and, if requested, top bid, attributes and last sale.
+    pub async fn retrieve_tokens(&self, params: TokensRequest) -> Result<TokensResponse, MagicedenApiError> {
+        let query_parameters = params.to_query_string()?;
+        let res = self.send(self.client.get(self.url.retrieve_tokens(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Retrieve collections with floor, top bid, volume and royalty stats.
+    pub async fn retrieve_collections(&self, params: CollectionsRequest) -> Result<CollectionsResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_collections(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Stream all collections matching `params`, following the continuation until the API is exhausted.
+    pub fn collections_stream(&self, params: CollectionsRequest) -> BoxStream<'static, Result<CollectionDetails, MagicedenApiError>> {
+        self.collections_stream_with_options(params, StreamOptions::default())
+    }
+
+    /// Stream collections matching `params` with an optional item cap and page size.
+    pub fn collections_stream_with_options(
+        &self,
+        params: CollectionsRequest,
+        options: StreamOptions,
+    ) -> BoxStream<'static, Result<CollectionDetails, MagicedenApiError>> {
+        self.stream_pages(
+            params,
+            options,
+            |client, params| async move { client.retrieve_collections(params).await },
+            |res| Page { items: res.collections, continuation: res.continuation },
+        )
+    }
+
+    /// Retrieve the tokens owned by `user` with ownership, floor ask and, if requested, top bid.
+    pub async fn retrieve_user_tokens(&self, user: &str, params: UserTokensRequest) -> Result<UserTokensResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_user_tokens(&self.chain, user, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Retrieve sales, e.g. of a collection or token.
+    pub async fn retrieve_sales(&self, params: SalesRequest) -> Result<SalesResponse, MagicedenApiError> {
+        let query_parameters = params.to_query_string()?;
+        let res = self.send(self.client.get(self.url.retrieve_sales(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Retrieve the activity of a collection, e.g. sales, new asks and bids, cancellations, transfers and mints.
+    pub async fn retrieve_collection_activity(&self, params: CollectionActivityRequest) -> Result<ActivityResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_collection_activity(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Stream the activity of a collection, following the continuation until the API is exhausted.
+    pub fn collection_activity_stream(&self, params: CollectionActivityRequest) -> BoxStream<'static, Result<Activity, MagicedenApiError>> {
+        self.collection_activity_stream_with_options(params, StreamOptions::default())
+    }
+
+    /// Stream the activity of a collection with an optional item cap and page size.
+    pub fn collection_activity_stream_with_options(
+        &self,
+        params: CollectionActivityRequest,
+        options: StreamOptions,
+    ) -> BoxStream<'static, Result<Activity, MagicedenApiError>> {
+        self.stream_pages(
+            params,
+            options,
+            |client, params| async move { client.retrieve_collection_activity(params).await },
+            |res| Page { items: res.activities, continuation: res.continuation },
+        )
+    }
+
+    /// Retrieve the activity of one or more users.
+    pub async fn retrieve_user_activity(&self, params: UserActivityRequest) -> Result<ActivityResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_user_activity(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Stream the activity of one or more users, following the continuation until the API is exhausted.
+    pub fn user_activity_stream(&self, params: UserActivityRequest) -> BoxStream<'static, Result<Activity, MagicedenApiError>> {
+        self.user_activity_stream_with_options(params, StreamOptions::default())
+    }
+
+    /// Stream the activity of one or more users with an optional item cap and page size.
+    pub fn user_activity_stream_with_options(
+        &self,
+        params: UserActivityRequest,
+        options: StreamOptions,
+    ) -> BoxStream<'static, Result<Activity, MagicedenApiError>> {
+        self.stream_pages(
+            params,
+            options,
+            |client, params| async move { client.retrieve_user_activity(params).await },
+            |res| Page { items: res.activities, continuation: res.continuation },
+        )
+    }
+
+    /// Retrieve the owners of a collection, contract, token or attribute, largest holder first.
+    pub async fn retrieve_owners(&self, params: OwnersRequest) -> Result<OwnersResponse, MagicedenApiError> {
+        let query_parameters = params.to_query_string()?;
+        let res = self.send(self.client.get(self.url.retrieve_owners(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Compute the holder concentration of a collection from its `top_n` largest holders and its token and owner
+    /// count. The owners are requested in pages of 500, `top_n` must be between 1 and 10000.
+    pub async fn holder_concentration(&self, collection: &str, top_n: u16) -> Result<HolderConcentration, MagicedenApiError> {
+        if top_n == 0 || u32::from(top_n) > MAX_OWNERS_OFFSET {
+            return Err(MagicedenApiError::InvalidArgument(format!("top_n must be between 1 and {MAX_OWNERS_OFFSET}, got {top_n}")));
+        }
+        let req = CollectionsRequest { id: Some(collection.to_string()), ..Default::default() };
+        let stats = self.retrieve_collections(req).await?.collections.into_iter().next();
+        let Some((Some(token_count), Some(owner_count))) = stats.map(|c| (c.token_count, c.owner_count)) else {
+            return Err(MagicedenApiError::Other(format!("No token and owner count for collection {collection}")));
+        };
+
+        let mut owners = Vec::with_capacity(usize::from(top_n));
+        while owners.len() < usize::from(top_n) {
+            // At most OWNERS_PAGE_SIZE, so the cast cannot truncate
+            let limit = usize::from(top_n).saturating_sub(owners.len()).min(usize::from(OWNERS_PAGE_SIZE)) as u16;
+            let req = OwnersRequest {
+                collection: Some(collection.to_string()),
+                offset: Some(owners.len() as u32),
+                limit: Some(limit),
+                ..Default::default()
+            };
+            let page = self.retrieve_owners(req).await?.owners;
+            let exhausted = page.len() < usize::from(limit);
+            owners.extend(page);
+            if exhausted {
+                break;
             }
-            Err(e) => Err(MagicedenApiError::Reqwest(e)),
         }
+        Ok(HolderConcentration::new(&owners, usize::from(top_n), token_count, owner_count))
+    }
+
+    /// Retrieve all attribute keys and values of a collection with their token count.
+    pub async fn retrieve_attributes_all(&self, collection: &str) -> Result<AttributesAllResponse, MagicedenApiError> {
+        let res = self.send(self.client.get(self.url.retrieve_attributes_all(&self.chain, collection)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Retrieve the attributes of a collection with floor asks, top bid and recent sales.
+    pub async fn explore_attributes(
+        &self,
+        collection: &str,
+        params: ExploreAttributesRequest,
+    ) -> Result<ExploreAttributesResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.explore_attributes(&self.chain, collection, query_parameters)), true).await?;
+        decode_response(res).await
+    }
+
+    /// Retrieve the stats of all traits of a collection: token count, floor ask, top bid and recent sales. Combines
+    /// the attributes all and explore endpoints.
+    pub async fn retrieve_collection_attributes(&self, collection: &str) -> Result<CollectionAttributes, MagicedenApiError> {
+        let all = self.retrieve_attributes_all(collection).await?;
+
+        let client = self.clone();
+        let collection = collection.to_string();
+        let explored = paginate(None, None, move |continuation| {
+            let client = client.clone();
+            let collection = collection.clone();
+            let params = ExploreAttributesRequest { include_top_bid: Some(true), continuation, ..Default::default() };
+            async move {
+                let res = client.explore_attributes(&collection, params).await?;
+                Ok(Page { items: res.attributes, continuation: res.continuation })
+            }
+        })
+        .try_collect()
+        .await?;
+
+        Ok(CollectionAttributes::new(all.attributes, explored))
+    }
+
+    /// Retrieve the aggregated ask or bid depth of a token or collection, including pool orders.
+    pub async fn retrieve_order_depth(&self, params: OrderDepthRequest) -> Result<OrderDepthResponse, MagicedenApiError> {
+        let query_parameters = serde_url_params::to_string(&params)?;
+        let res = self.send(self.client.get(self.url.retrieve_order_depth(&self.chain, query_parameters)), true).await?;
+        decode_response(res).await
     }
 
     pub async fn buy_tokens(&self, req: BuyTokensRequest) -> Result<BuyTokensResponse, MagicedenApiError> {
-        let res = self.client.post(self.url.buy_tokens(&self.chain)).json(&req).send().await;
-        match res {
-            Ok(res) => {
-                let status_code = res.status();
-                let body = res.text().await?;
-
-                if status_code == StatusCode::BAD_REQUEST {
-                    let res = serde_json::from_str::<MagicedenBuyTokensErrorResponse>(&body);
-                    return match res {
-                        Ok(r) => Err(MagicedenApiError::MagicedenBuyTokensError(r)),
-                        Err(e) => {
-                            let e = MagicedenErrorParseResponse { status_code: status_code.as_u16(), body, error: e.to_string() };
-                            Err(MagicedenApiError::ResponseParseError(e))
-                        }
-                    };
-                } else if status_code == StatusCode::GONE {
-                    let res = serde_json::from_str::<MagicedenOrderAlreadyFilledError>(&body);
-                    return match res {
-                        Ok(r) => Err(MagicedenApiError::MagicedenOrderAlreadyFilledError(r)),
-                        Err(e) => {
-                            let e = MagicedenErrorParseResponse { body, status_code: status_code.as_u16(), error: e.to_string() };
-                            Err(MagicedenApiError::ResponseParseError(e))
-                        }
-                    };
-                }
-                if status_code != 200 {
-                    return Err(MagicedenApiError::ServerError(ServerError { status_code: status_code.as_u16(), body }));
-                }
-
-                let res = serde_json::from_str::<BuyTokensResponse>(&body);
-                match res {
-                    Ok(r) => Ok(r),
-                    Err(e) => {
-                        let e = MagicedenErrorParseResponse { body, status_code: status_code.as_u16(), error: e.to_string() };
-                        Err(MagicedenApiError::ResponseParseError(e))
-                    }
-                }
+        let res = self.send(self.client.post(self.url.buy_tokens(&self.chain)).json(&req), false).await?;
+        decode_response(res).await
+    }
+
+    /// Mint tokens of a collection or token. Fails with [`MagicedenApiError::Mint`] if the mint is not possible in
+    /// its current phase, e.g. not started, sold out or max per wallet reached, either as error response or as errors
+    /// of a response without steps to execute. Other errors are returned as usual.
+    pub async fn mint_tokens(&self, req: MintRequest) -> Result<MintTokensResponse, MagicedenApiError> {
+        let res = self.send(self.client.post(self.url.mint_tokens(&self.chain)).json(&req), false).await?;
+        let res: MintTokensResponse = decode_response(res).await.map_err(|e| match e {
+            MagicedenApiError::BadRequest(res) => {
+                let errors = vec![BuyTokenError { message: res.message().unwrap_or_default().to_string(), order_id: None }];
+                MintError::from_errors(errors).map_or(MagicedenApiError::BadRequest(res), MagicedenApiError::Mint)
+            }
+            e => e,
+        })?;
+
+        // Nothing to execute, e.g. every item failed. Errors not caused by the mint phase are left to the caller.
+        if res.steps.iter().all(|step| step.items.is_empty()) {
+            if let Some(error) = MintError::from_errors(res.errors.clone()) {
+                return Err(MagicedenApiError::Mint(error));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Create listings. Returns the approval and signature steps to post the listings.
+    pub async fn list_tokens(&self, req: ListTokensRequest) -> Result<ListTokensResponse, MagicedenApiError> {
+        let res = self.send(self.client.post(self.url.list_tokens(&self.chain)).json(&req), false).await?;
+        decode_response(res).await
+    }
+
+    /// Place token, collection, attribute or token set bids. Returns the wrapping, approval and signature steps to
+    /// post the bids.
+    pub async fn place_bids(&self, req: BidRequest) -> Result<PlaceBidsResponse, MagicedenApiError> {
+        let res = self.send(self.client.post(self.url.place_bids(&self.chain)).json(&req), false).await?;
+        decode_response(res).await
+    }
+
+    /// Cancel orders by id, or all orders of a maker and order kind. Orders that are natively off-chain cancellable
+    /// return a signature step, all others a cancellation transaction.
+    pub async fn cancel_orders(&self, req: CancelRequest) -> Result<CancelOrdersResponse, MagicedenApiError> {
+        let res = self.send(self.client.post(self.url.cancel_orders(&self.chain)).json(&req), false).await?;
+        decode_response(res).await
+    }
+
+    /// Check the status of a step item. For transaction steps the hash of the sent transaction must be set with
+    /// [`BuyTokenCheck::with_tx_hash`]. Fails with [`MagicedenApiError::InvalidStep`] if the check uses another method
+    /// than POST.
+    pub async fn check_step_status(&self, check: &BuyTokenCheck) -> Result<StepStatus, MagicedenApiError> {
+        if !matches!(check.method, Method::Post) {
+            return Err(MagicedenApiError::InvalidStep(format!("unsupported method of check endpoint {}", check.endpoint)));
+        }
+        let url = self.url.step_endpoint(&self.chain, &check.endpoint);
+        let res = self.send(self.client.post(url).json(&check.body), true).await?;
+        decode_response(res).await
+    }
+
+    /// Poll the status of a step item every `interval` until it succeeded or failed. Returns
+    /// [`MagicedenApiError::Timeout`] if no final status is reached within `timeout`, including time spent in retries
+    /// of a single poll.
+    pub async fn wait_for_step(
+        &self,
+        check: &BuyTokenCheck,
+        timeout: Duration,
+        interval: Duration,
+    ) -> Result<StepStatus, MagicedenApiError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = tokio::time::timeout_at(deadline, self.check_step_status(check))
+                .await
+                .map_err(|_| MagicedenApiError::Timeout(timeout))??;
+            if status.status.is_final() {
+                return Ok(status);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(MagicedenApiError::Timeout(timeout));
             }
-            Err(e) => Err(MagicedenApiError::Reqwest(e)),
+            tokio::time::sleep(interval.min(remaining)).await;
         }
     }
+
+    /// Post the signature of a signature step to the endpoint given by the step. Fails with
+    /// [`MagicedenApiError::InvalidStep`] if the step uses another method than POST.
+    pub async fn post_signature(&self, post: &PostData, signature: &str) -> Result<Value, MagicedenApiError> {
+        if !matches!(post.method, Method::Post) {
+            return Err(MagicedenApiError::InvalidStep(format!("unsupported method of signature endpoint {}", post.endpoint)));
+        }
+        let url = self.url.step_endpoint(&self.chain, &post.endpoint);
+        let res = self.send(self.client.post(url).query(&[("signature", signature)]).json(&post.body), false).await?;
+        decode_response(res).await
+    }
+
+    /// Sell tokens into bids. Returns the steps to execute the sale.
+    pub async fn sell_tokens(&self, req: SellTokensRequest) -> Result<SellTokensResponse, MagicedenApiError> {
+        let res = self.send(self.client.post(self.url.sell_tokens(&self.chain)).json(&req), false).await?;
+        decode_response(res).await
+    }
+}
+
+/// Decode a successful response into `T`, or classify the error by status code.
+async fn decode_response<T: DeserializeOwned>(res: Response) -> Result<T, MagicedenApiError> {
+    let status_code = res.status();
+    let retry_after = retry_after(res.headers());
+    let body = res.text().await?;
+
+    if !status_code.is_success() {
+        return Err(MagicedenApiError::from_status(status_code, body, retry_after));
+    }
+
+    serde_json::from_str::<T>(&body).map_err(|e| {
+        let e = MagicedenErrorParseResponse { body, status_code: status_code.as_u16(), error: e.to_string() };
+        MagicedenApiError::ResponseParseError(e)
+    })
 }
 
 #[cfg(test)]
@@ -125,6 +524,41 @@ mod tests {
         assert_eq!(res.path.first().unwrap().token_id, "837");
     }
 
+    #[test]
+    fn can_deserialize_buy_response_with_pre_signature() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("resources/response_buy_presignature.json");
+        let res = std::fs::read_to_string(d).unwrap();
+        let res: BuyTokensResponse = serde_json::from_str(&res).unwrap();
+        let sig = res.steps[0].items[0].data.as_ref().unwrap().as_signature().unwrap();
+        assert!(sig.sign.message.as_ref().unwrap().starts_with("Sign in to Blur"));
+        assert_eq!(sig.post.as_ref().unwrap().endpoint, "/execute/pre-signature/v1");
+        assert!(res.steps[1].items[0].data.as_ref().unwrap().as_transaction().is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_api_key() {
+        let cfg = MagicedenApiConfig { api_key: Some("key\nwith newline".to_string()), ..Default::default() };
+        let res = MagicedenClient::try_new(cfg);
+        assert!(matches!(res, Err(MagicedenApiError::Config(_))));
+    }
+
+    #[cfg(feature = "alloy")]
+    #[test]
+    fn converts_to_alloy_primitives() {
+        use alloy_primitives::{Address, U256};
+
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("resources/response_buy_magiceden.json");
+        let res = std::fs::read_to_string(d).unwrap();
+        let res: BuyTokensResponse = serde_json::from_str(&res).unwrap();
+        let path = res.path.first().unwrap();
+        assert_eq!(path.raw_quote.to_alloy().unwrap(), U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(path.currency.to_alloy().unwrap(), Address::ZERO);
+        let tx = res.steps.iter().flat_map(|s| &s.items).next().unwrap().data.as_ref().unwrap().as_transaction().unwrap();
+        assert_eq!(tx.value.to_alloy().unwrap(), U256::from(2_000_000_000_000_000_000u128));
+    }
+
     #[test]
     fn can_deserialize_asks_response() {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -132,6 +566,6 @@ mod tests {
         println!("{}", d.display());
         let res = std::fs::read_to_string(d).unwrap();
         let res: AsksResponse = serde_json::from_str(&res).unwrap();
-        assert_eq!(res.orders.first().unwrap().id, "0x5844792a36ff5966a325d2180ebda80f8f63a7f3d4585e1c88615a111ce42942");
+        assert_eq!(res.orders.first().unwrap().id.to_string(), "0x5844792a36ff5966a325d2180ebda80f8f63a7f3d4585e1c88615a111ce42942");
     }
 }
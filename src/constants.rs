@@ -1,4 +1,5 @@
 pub const PROTOCOL_VERSION: &str = "v3";
+pub const PATH_PREFIX: &str = "rtp/{chain}";
 
-pub const API_BASE_MAINNET: &str = "https://api-mainnet.magiceden.dev/";
-pub const API_BASE_TESTNET: &str = "https://xxx";
+// Magic Eden serves mainnets and testnets from the same host
+pub const API_BASE: &str = "https://api-mainnet.magiceden.dev/";
@@ -0,0 +1,143 @@
+use crate::{
+    client::MagicedenClient,
+    types::{
+        api::{BuyTokenItemData, ExecuteStep, SignData, SignatureKind, Status, StepId, StepItemData, StepStatus},
+        primitives::{Bytes, B256},
+        MagicedenApiError,
+    },
+};
+use std::{future::Future, time::Duration};
+
+/// Signs the signature steps of an execute response, e.g. with a local wallet or a remote signer.
+pub trait StepSigner {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Sign EIP-712 typed data (eth_signTypedData_v4). [`SignData::to_typed_data`] returns the standard typed data.
+    fn sign_typed_data(&self, data: &SignData) -> impl Future<Output = Result<Bytes, Self::Error>> + Send;
+
+    /// Sign an EIP-191 message (personal_sign).
+    fn sign_message(&self, message: &str) -> impl Future<Output = Result<Bytes, Self::Error>> + Send;
+}
+
+/// Sends the transaction steps of an execute response and returns the transaction hash.
+pub trait TransactionSender {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Send the transaction and return its hash once it is mined. The next item is executed right after this returns,
+    /// and items without a check endpoint are not confirmed otherwise, e.g. an approval the following sale depends on.
+    /// Return an error if the transaction reverted.
+    fn send_transaction(&self, tx: &BuyTokenItemData) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+}
+
+/// Options of the [`StepExecutor`].
+#[derive(Debug, Clone)]
+pub struct ExecuteOptions {
+    /// Maximum time to wait for a step item to reach a final status.
+    pub check_timeout: Duration,
+    /// Time between two status checks.
+    pub check_interval: Duration,
+}
+
+impl Default for ExecuteOptions {
+    fn default() -> Self {
+        Self { check_timeout: Duration::from_secs(300), check_interval: Duration::from_secs(2) }
+    }
+}
+
+/// Result of an executed step item.
+#[derive(Debug, Clone)]
+pub struct ExecutedItem {
+    pub step_id: StepId,
+    /// Hash of the sent transaction, for transaction items.
+    pub tx_hash: Option<B256>,
+    /// Signature, for signature items.
+    pub signature: Option<Bytes>,
+    /// Final status reported by the check endpoint, if the item has one.
+    pub status: Option<StepStatus>,
+}
+
+/// Drives the steps of an execute response, e.g. buy, sell, list or bid, to completion.
+///
+/// Items are executed in order. Complete items are skipped, signatures are posted back where required and the
+/// check endpoint is polled after each item until it reports a final status, before the next item runs. Items
+/// without check endpoint rely on the [`TransactionSender`] returning only once the transaction is mined.
+///
+/// The steps are executed as given and are not requested again after an item completes. The execute endpoints return
+/// the data of all steps upfront, e.g. the sale transaction together with the approval it depends on. If a step needs
+/// fresh data, request the steps again and pass them to [`StepExecutor::execute`]; complete items are skipped.
+#[derive(Debug, Clone)]
+pub struct StepExecutor<S, T> {
+    client: MagicedenClient,
+    signer: S,
+    sender: T,
+    options: ExecuteOptions,
+}
+
+impl<S: StepSigner, T: TransactionSender> StepExecutor<S, T> {
+    pub fn new(client: MagicedenClient, signer: S, sender: T) -> Self {
+        Self { client, signer, sender, options: ExecuteOptions::default() }
+    }
+
+    pub fn with_options(mut self, options: ExecuteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Execute all incomplete items of `steps`. Fails on the first error or failed status.
+    pub async fn execute(&self, steps: impl IntoIterator<Item = ExecuteStep>) -> Result<Vec<ExecutedItem>, MagicedenApiError> {
+        let mut executed = Vec::new();
+        for step in steps {
+            for item in step.items {
+                if matches!(item.status, Status::Complete) {
+                    continue;
+                }
+                let Some(data) = item.data else {
+                    return Err(MagicedenApiError::InvalidStep(format!("incomplete item of step {} without data", step.id)));
+                };
+
+                let mut result = ExecutedItem { step_id: step.id.clone(), tx_hash: None, signature: None, status: None };
+                let mut check = item.check;
+                match data {
+                    StepItemData::Transaction(tx) => {
+                        let tx_hash = self.sender.send_transaction(&tx).await.map_err(|e| MagicedenApiError::Transaction(Box::new(e)))?;
+                        // The check carries the hash and hands it back to the result once polled
+                        match check.take() {
+                            Some(c) => check = Some(c.with_tx_hash(tx_hash)),
+                            None => result.tx_hash = Some(tx_hash),
+                        }
+                    }
+                    StepItemData::Signature(sig) => {
+                        let signature = self.sign(&sig.sign).await?;
+                        if let Some(post) = &sig.post {
+                            self.client.post_signature(post, signature.as_str()).await?;
+                        }
+                        result.signature = Some(signature);
+                    }
+                }
+
+                if let Some(check) = check {
+                    let status = self.client.wait_for_step(&check, self.options.check_timeout, self.options.check_interval).await?;
+                    if !status.status.is_success() {
+                        return Err(MagicedenApiError::StepFailed(Box::new(status)));
+                    }
+                    result.tx_hash = result.tx_hash.take().or(check.body.tx_hash);
+                    result.status = Some(status);
+                }
+                executed.push(result);
+            }
+        }
+        Ok(executed)
+    }
+
+    async fn sign(&self, sign: &SignData) -> Result<Bytes, MagicedenApiError> {
+        let res = match sign.signature_kind {
+            SignatureKind::Eip712 => self.signer.sign_typed_data(sign).await,
+            SignatureKind::Eip191 => {
+                let message =
+                    sign.message.as_deref().ok_or_else(|| MagicedenApiError::InvalidStep("EIP-191 step without message".to_string()))?;
+                self.signer.sign_message(message).await
+            }
+        };
+        res.map_err(|e| MagicedenApiError::Signer(Box::new(e)))
+    }
+}
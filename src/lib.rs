@@ -4,7 +4,23 @@ pub mod client;
 /// This module contains constants used by the client.
 mod constants;
 
+/// This module contains the executor driving the steps of execute responses.
+pub mod executor;
+
+/// This module contains the client-side rate limiter.
+pub mod rate_limit;
+
+/// This module contains the retry policy for failed requests.
+pub mod retry;
+
+/// This module contains the helpers for streaming paginated endpoints.
+pub mod stream;
+
 /// This module contains the core type definitions for the client.
 pub mod types;
 
 pub use client::{MagicedenApiConfig, MagicedenClient};
+pub use executor::{ExecuteOptions, StepExecutor, StepSigner, TransactionSender};
+pub use rate_limit::RateLimit;
+pub use retry::RetryPolicy;
+pub use stream::StreamOptions;
@@ -0,0 +1,72 @@
+use std::{sync::Mutex, time::Duration};
+use tokio::time::Instant;
+
+/// Client-side rate limit, usually matching the tier of the API key.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    /// Sustained number of requests per second.
+    pub requests_per_second: u32,
+    /// Number of requests that can be sent at once before throttling kicks in.
+    pub burst: u32,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket shared by all endpoints and all clones of a client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    rate: f64,
+    capacity: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: &RateLimit) -> Self {
+        let rate = f64::from(limit.requests_per_second.max(1));
+        let capacity = f64::from(limit.burst.max(1));
+        Self { rate, capacity, bucket: Mutex::new(Bucket { tokens: capacity, last_refill: Instant::now() }) }
+    }
+
+    /// Wait until a request may be sent. Callers are served in the order they reserved their token.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+            bucket.last_refill = now;
+
+            // Reserve a token even if the bucket is empty. The debt is paid by waiting.
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn allows_burst_then_throttles() {
+        let limiter = RateLimiter::new(&RateLimit { requests_per_second: 2, burst: 3 });
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed().as_millis(), 1000);
+    }
+}
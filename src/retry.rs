@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+use std::time::Duration;
+
+/// Retry policy for requests that failed with a rate limit, a server error or a connection error.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Backoff before the first retry. Doubled for every further retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential backoff.
+    pub max_backoff: Duration,
+    /// Randomize the backoff to avoid retrying in lockstep with other clients.
+    pub jitter: bool,
+    /// Respect the `Retry-After` header of 429 and 503 responses. A `Retry-After` longer than `max_backoff` is not
+    /// waited for, the response is returned instead.
+    pub respect_retry_after: bool,
+    /// Also retry non-idempotent requests like `buy_tokens`. Retrying those can execute an action twice.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns true if a request with the given idempotency may be retried after `attempt` attempts.
+    pub(crate) fn allows_retry(&self, attempt: u32, idempotent: bool) -> bool {
+        attempt < self.max_attempts && (idempotent || self.retry_non_idempotent)
+    }
+
+    /// Exponential backoff for the given attempt (starting at 1), capped at `max_backoff`.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.initial_backoff.saturating_mul(factor).min(self.max_backoff);
+        if self.jitter && !delay.is_zero() {
+            // Equal jitter: keep at least half of the delay.
+            let half = delay / 2;
+            half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+
+    /// Delay before the next attempt. A `Retry-After` header takes precedence over the backoff. Returns `None` if the
+    /// `Retry-After` exceeds `max_backoff` and the request should not be retried.
+    pub(crate) fn delay(&self, attempt: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+        if self.respect_retry_after {
+            if let Some(delay) = headers.and_then(retry_after) {
+                return (delay <= self.max_backoff).then_some(delay);
+            }
+        }
+        Some(self.backoff(attempt))
+    }
+}
+
+/// Returns true for responses worth retrying: 429 and 5xx.
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Returns true for transport errors worth retrying.
+pub(crate) fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// Parses the `Retry-After` header, given either in seconds or as HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((date - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy { jitter: false, max_backoff: Duration::from_secs(3), ..Default::default() };
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(policy.backoff(4), Duration::from_secs(3));
+
+        let policy = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn only_retries_idempotent_requests_by_default() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows_retry(1, true));
+        assert!(!policy.allows_retry(3, true));
+        assert!(!policy.allows_retry(1, false));
+
+        let policy = RetryPolicy { retry_non_idempotent: true, ..Default::default() };
+        assert!(policy.allows_retry(1, false));
+    }
+
+    #[test]
+    fn parses_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        let policy = RetryPolicy { jitter: false, ..Default::default() };
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(policy.delay(1, Some(&headers)), Some(Duration::from_secs(2)));
+        assert_eq!(policy.delay(1, None), Some(Duration::from_millis(500)));
+
+        // Longer than max_backoff
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("86400"));
+        assert_eq!(policy.delay(1, Some(&headers)), None);
+    }
+}
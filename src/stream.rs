@@ -0,0 +1,148 @@
+use crate::types::{
+    api::{AsksRequest, BidsRequest, CollectionActivityRequest, CollectionsRequest, UserActivityRequest},
+    MagicedenApiError,
+};
+use futures::{stream, Stream};
+use std::{collections::VecDeque, future::Future};
+
+/// Options for streaming a paginated endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct StreamOptions {
+    /// Stop after this many items in total. `None` streams until the API is exhausted.
+    pub max_items: Option<usize>,
+    /// Amount of items requested per page. Overrides the `limit` of the request.
+    pub page_size: Option<u16>,
+}
+
+/// One page of a paginated response.
+pub(crate) struct Page<T> {
+    pub items: Vec<T>,
+    pub continuation: Option<String>,
+}
+
+/// Request of an endpoint paginated by continuation.
+pub(crate) trait PageRequest: Clone + Send + 'static {
+    fn set_limit(&mut self, limit: u16);
+
+    fn continuation_mut(&mut self) -> &mut Option<String>;
+}
+
+macro_rules! page_request {
+    ($($request:ty),*) => {
+        $(
+            impl PageRequest for $request {
+                fn set_limit(&mut self, limit: u16) {
+                    self.limit = Some(limit);
+                }
+
+                fn continuation_mut(&mut self) -> &mut Option<String> {
+                    &mut self.continuation
+                }
+            }
+        )*
+    };
+}
+
+page_request!(AsksRequest, BidsRequest, CollectionsRequest, CollectionActivityRequest, UserActivityRequest);
+
+struct PageState<T, F> {
+    fetch: F,
+    buffer: VecDeque<T>,
+    continuation: Option<String>,
+    remaining: Option<usize>,
+    exhausted: bool,
+}
+
+/// Turns a page fetcher into a stream of items. `fetch` is called with the continuation of the previous page.
+///
+/// The stream ends when the API returns no continuation, an empty page or the same continuation again, when
+/// `max_items` have been yielded, or after the first error.
+pub(crate) fn paginate<T, F, Fut>(
+    continuation: Option<String>,
+    max_items: Option<usize>,
+    fetch: F,
+) -> impl Stream<Item = Result<T, MagicedenApiError>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Page<T>, MagicedenApiError>>,
+{
+    let state = PageState { fetch, buffer: VecDeque::new(), continuation, remaining: max_items, exhausted: false };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.remaining == Some(0) {
+                return None;
+            }
+            if let Some(item) = state.buffer.pop_front() {
+                state.remaining = state.remaining.map(|r| r - 1);
+                return Some((Ok(item), state));
+            }
+            if state.exhausted {
+                return None;
+            }
+
+            let sent = state.continuation.take();
+            match (state.fetch)(sent.clone()).await {
+                Ok(page) => {
+                    state.exhausted = page.items.is_empty() || page.continuation.is_none() || page.continuation == sent;
+                    state.continuation = page.continuation;
+                    state.buffer.extend(page.items);
+                }
+                Err(e) => {
+                    state.exhausted = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, StreamExt};
+
+    fn collect<T>(s: impl Stream<Item = Result<T, MagicedenApiError>>) -> Vec<Result<T, MagicedenApiError>> {
+        block_on(s.collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn follows_continuation_until_exhausted() {
+        let items = collect(paginate(None, None, |continuation| async move {
+            Ok(match continuation.as_deref() {
+                None => Page { items: vec![1, 2], continuation: Some("a".to_string()) },
+                Some("a") => Page { items: vec![3], continuation: Some("b".to_string()) },
+                _ => Page { items: vec![4], continuation: None },
+            })
+        }));
+        assert_eq!(items.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stops_on_repeated_continuation_and_empty_page() {
+        let items = collect(paginate(None, None, |_| async { Ok(Page { items: vec![1], continuation: Some("same".to_string()) }) }));
+        assert_eq!(items.len(), 2);
+
+        let items = collect(paginate(None, None, |_| async { Ok(Page::<u8> { items: vec![], continuation: Some("next".to_string()) }) }));
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn respects_max_items() {
+        let mut calls = 0;
+        let items = collect(paginate(None, Some(3), |_| {
+            calls += 1;
+            let continuation = Some(calls.to_string());
+            async move { Ok(Page { items: vec![1, 2], continuation }) }
+        }));
+        assert_eq!(items.len(), 3);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn ends_after_error() {
+        let items = collect(paginate(None, None, |_| async { Err::<Page<u8>, _>(MagicedenApiError::Other("boom".to_string())) }));
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
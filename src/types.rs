@@ -1,11 +1,14 @@
 pub mod api;
+pub mod primitives;
 
-use crate::types::api::{
-    MagicedenBuyTokensErrorResponse, MagicedenErrorParseResponse, MagicedenErrorResponse, MagicedenOrderAlreadyFilledError, ServerError,
+use crate::{
+    retry::{is_retryable_error, is_retryable_status},
+    types::api::{ApiErrorResponse, MagicedenErrorParseResponse, MintError, StepStatus},
 };
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use strum::{AsRefStr, EnumString};
+use std::{fmt, time::Duration};
+use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -15,26 +18,131 @@ pub enum MagicedenApiError {
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
-    ResponseParseError(#[from] MagicedenErrorParseResponse),
-    #[error(transparent)]
-    ServerError(#[from] ServerError),
-    #[error(transparent)]
-    MagicedenError(#[from] MagicedenErrorResponse),
+    UrlParams(#[from] serde_url_params::Error),
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+    // An argument of the call is out of range or invalid
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
     #[error(transparent)]
-    MagicedenBuyTokensError(#[from] MagicedenBuyTokensErrorResponse),
-    #[error(transparent)]
-    MagicedenOrderAlreadyFilledError(#[from] MagicedenOrderAlreadyFilledError),
+    ResponseParseError(#[from] MagicedenErrorParseResponse),
+    // 400, e.g. invalid parameters or no fillable orders
+    #[error("Bad request: {0}")]
+    BadRequest(Box<ApiErrorResponse>),
+    // 401, missing or invalid api key
+    #[error("Unauthorized: {0}")]
+    Unauthorized(Box<ApiErrorResponse>),
+    // 403, api key lacks access to the endpoint
+    #[error("Forbidden: {0}")]
+    Forbidden(Box<ApiErrorResponse>),
+    // 404
+    #[error("Not found: {0}")]
+    NotFound(Box<ApiErrorResponse>),
+    // 410, e.g. order already filled
+    #[error("Gone: {0}")]
+    Gone(Box<ApiErrorResponse>),
+    // 429
+    #[error("Rate limited: {0}")]
+    RateLimited(Box<ApiErrorResponse>),
+    // 5xx
+    #[error("Server error: {0}")]
+    ServerError(Box<ApiErrorResponse>),
+    // Any other non-success status
+    #[error("Unexpected status: {0}")]
+    UnexpectedStatus(Box<ApiErrorResponse>),
+    // The signer of the step executor failed
+    #[error("Signer error: {0}")]
+    Signer(Box<dyn std::error::Error + Send + Sync>),
+    // The transaction sender of the step executor failed
+    #[error("Transaction error: {0}")]
+    Transaction(Box<dyn std::error::Error + Send + Sync>),
+    // A step of an execute response cannot be executed, e.g. an incomplete item without data
+    #[error("Invalid step: {0}")]
+    InvalidStep(String),
+    // The check endpoint reported a failed step
+    #[error("Step failed: {}", .0.details.as_deref().unwrap_or("no details"))]
+    StepFailed(Box<StepStatus>),
+    // The mint is not possible in its current phase, e.g. not started, sold out or max per wallet reached
+    #[error("Mint failed: {0}")]
+    Mint(MintError),
+    // Polling did not reach a final status in time
+    #[error("Timed out after {0:?}")]
+    Timeout(Duration),
     #[error("{0}")]
     Other(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, EnumString, AsRefStr, Default)]
-#[serde(rename_all = "lowercase")]
-#[strum(serialize_all = "lowercase")]
+impl MagicedenApiError {
+    /// Classifies a non-success response by its status code.
+    pub(crate) fn from_status(status_code: StatusCode, body: String, retry_after: Option<Duration>) -> Self {
+        let res = Box::new(ApiErrorResponse::new(status_code.as_u16(), body, retry_after));
+        match status_code {
+            StatusCode::BAD_REQUEST => Self::BadRequest(res),
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(res),
+            StatusCode::FORBIDDEN => Self::Forbidden(res),
+            StatusCode::NOT_FOUND => Self::NotFound(res),
+            StatusCode::GONE => Self::Gone(res),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(res),
+            s if s.is_server_error() => Self::ServerError(res),
+            _ => Self::UnexpectedStatus(res),
+        }
+    }
+
+    /// The error response of the API, if the request failed with a non-success status.
+    pub fn api_error(&self) -> Option<&ApiErrorResponse> {
+        use MagicedenApiError::*;
+        match self {
+            BadRequest(e) | Unauthorized(e) | Forbidden(e) | NotFound(e) | Gone(e) | RateLimited(e) | ServerError(e)
+            | UnexpectedStatus(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of the failed request, if a response was received.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::Reqwest(e) => e.status().map(|s| s.as_u16()),
+            Self::ResponseParseError(e) => Some(e.status_code),
+            e => e.api_error().map(|e| e.status_code),
+        }
+    }
+
+    /// Returns true if the request may succeed when sent again: rate limits, server errors and connection failures.
+    /// Uses the same predicates as the retry policy of the client.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Reqwest(e) => is_retryable_error(e),
+            e => e.api_error().and_then(|e| StatusCode::from_u16(e.status_code).ok()).is_some_and(is_retryable_status),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumString, AsRefStr, EnumIter, Default)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
 pub enum Chain {
     #[default]
     Ethereum,
+    Polygon,
+    Base,
+    Arbitrum,
+    Bsc,
+    Avalanche,
+    #[serde(rename = "apechain")]
+    #[strum(serialize = "apechain")]
+    ApeChain,
+    Abstract,
+    Berachain,
+    Sei,
+    Monad,
+    // Testnets
     Goerli,
+    Sepolia,
+    BaseSepolia,
+    ArbitrumSepolia,
+    PolygonAmoy,
+    AbstractTestnet,
+    MonadTestnet,
 }
 impl fmt::Display for Chain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -42,10 +150,77 @@ impl fmt::Display for Chain {
     }
 }
 
+/// Static metadata of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainInfo {
+    pub chain_id: u64,
+    // Symbol of the native currency, e.g. ETH
+    pub native_symbol: &'static str,
+    pub native_decimals: u8,
+    // Address of the wrapped native token, e.g. WETH
+    pub wrapped_native: &'static str,
+    pub testnet: bool,
+}
+
+impl ChainInfo {
+    const fn new(chain_id: u64, native_symbol: &'static str, wrapped_native: &'static str, testnet: bool) -> Self {
+        Self { chain_id, native_symbol, native_decimals: 18, wrapped_native, testnet }
+    }
+}
+
 impl Chain {
-    pub fn is_test_chain(&self) -> bool {
+    /// Static metadata of the chain.
+    pub const fn info(&self) -> ChainInfo {
         use Chain::*;
-        matches!(self, Goerli)
+        match self {
+            Ethereum => ChainInfo::new(1, "ETH", "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", false),
+            Polygon => ChainInfo::new(137, "POL", "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270", false),
+            Base => ChainInfo::new(8453, "ETH", "0x4200000000000000000000000000000000000006", false),
+            Arbitrum => ChainInfo::new(42161, "ETH", "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1", false),
+            Bsc => ChainInfo::new(56, "BNB", "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c", false),
+            Avalanche => ChainInfo::new(43114, "AVAX", "0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7", false),
+            ApeChain => ChainInfo::new(33139, "APE", "0x48b62137EdfA95a428D35C09E44256a739F6B557", false),
+            Abstract => ChainInfo::new(2741, "ETH", "0x3439153EB7AF838Ad19d56E1571FBD09333C2809", false),
+            Berachain => ChainInfo::new(80094, "BERA", "0x6969696969696969696969696969696969696969", false),
+            Sei => ChainInfo::new(1329, "SEI", "0xE30feDd158A2e3b13e9badaeABaFc5516e95e8C7", false),
+            Monad => ChainInfo::new(143, "MON", "0x3bd359C1119dA7Da1D913D1C4D2B7c461115433A", false),
+            Goerli => ChainInfo::new(5, "ETH", "0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6", true),
+            Sepolia => ChainInfo::new(11155111, "ETH", "0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14", true),
+            BaseSepolia => ChainInfo::new(84532, "ETH", "0x4200000000000000000000000000000000000006", true),
+            ArbitrumSepolia => ChainInfo::new(421614, "ETH", "0x980B62Da83eFf3D4576C647993b0c1D7faf17c73", true),
+            PolygonAmoy => ChainInfo::new(80002, "POL", "0x360ad4f9a9A8EFe9A8DCB5f461c4Cc1047E1Dcf9", true),
+            AbstractTestnet => ChainInfo::new(11124, "ETH", "0x9EDCde0257F2386Ce177C3a7FCdd97787F0D841d", true),
+            MonadTestnet => ChainInfo::new(10143, "MON", "0x760AfE86e5de5fa0Ee542fc7B7B713e1c5425701", true),
+        }
+    }
+
+    /// Find the chain with the given numeric chain id.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        Self::iter().find(|chain| chain.chain_id() == chain_id)
+    }
+
+    #[inline]
+    pub fn chain_id(&self) -> u64 {
+        self.info().chain_id
+    }
+
+    #[inline]
+    pub fn native_symbol(&self) -> &'static str {
+        self.info().native_symbol
+    }
+
+    #[inline]
+    pub fn native_decimals(&self) -> u8 {
+        self.info().native_decimals
+    }
+
+    #[inline]
+    pub fn wrapped_native(&self) -> &'static str {
+        self.info().wrapped_native
+    }
+
+    pub fn is_test_chain(&self) -> bool {
+        self.info().testnet
     }
 
     #[inline]
@@ -54,17 +229,172 @@ impl Chain {
     }
 }
 
+impl TryFrom<u64> for Chain {
+    type Error = MagicedenApiError;
+
+    fn try_from(chain_id: u64) -> Result<Self, Self::Error> {
+        Self::from_chain_id(chain_id).ok_or_else(|| MagicedenApiError::InvalidArgument(format!("Unsupported chain id: {chain_id}")))
+    }
+}
+
 /// API endpoints
 #[derive(Debug, Clone)]
 pub struct ApiUrl {
+    // Base url including the protocol version, e.g. https://api-mainnet.magiceden.dev/v3
     pub base: String,
+    // Path prefix of the chain specific endpoints. `{chain}` is replaced by the chain name.
+    pub prefix: String,
 }
 
 impl ApiUrl {
+    pub fn new(base_url: &str, protocol_version: &str, prefix: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let protocol_version = protocol_version.trim_matches('/');
+        let base = if protocol_version.is_empty() { base_url.to_string() } else { format!("{base_url}/{protocol_version}") };
+        Self { base, prefix: prefix.trim_matches('/').to_string() }
+    }
+
+    fn chain_base(&self, chain: &Chain) -> String {
+        let prefix = self.prefix.replace("{chain}", chain.as_ref());
+        if prefix.is_empty() {
+            self.base.clone()
+        } else {
+            format!("{}/{}", self.base, prefix)
+        }
+    }
+
     pub fn retrieve_asks(&self, chain: &Chain, query_parameters: String) -> String {
-        format!("{}/rtp/{}/orders/asks/v5?{}", self.base, chain, query_parameters)
+        format!("{}/orders/asks/v5?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_bids(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/orders/bids/v6?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_tokens(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/tokens/v7?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_collections(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/collections/v7?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_user_tokens(&self, chain: &Chain, user: &str, query_parameters: String) -> String {
+        format!("{}/users/{}/tokens/v10?{}", self.chain_base(chain), user, query_parameters)
+    }
+    pub fn retrieve_sales(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/sales/v6?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_collection_activity(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/collections/activity/v6?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_user_activity(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/users/activity/v6?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_owners(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/owners/v2?{}", self.chain_base(chain), query_parameters)
+    }
+    pub fn retrieve_attributes_all(&self, chain: &Chain, collection: &str) -> String {
+        format!("{}/collections/{}/attributes/all/v4", self.chain_base(chain), collection)
+    }
+    pub fn explore_attributes(&self, chain: &Chain, collection: &str, query_parameters: String) -> String {
+        format!("{}/collections/{}/attributes/explore/v5?{}", self.chain_base(chain), collection, query_parameters)
+    }
+    pub fn retrieve_order_depth(&self, chain: &Chain, query_parameters: String) -> String {
+        format!("{}/orders/depth/v1?{}", self.chain_base(chain), query_parameters)
     }
     pub fn buy_tokens(&self, chain: &Chain) -> String {
-        format!("{}/rtp/{}/execute/buy/v7", self.base, chain)
+        format!("{}/execute/buy/v7", self.chain_base(chain))
+    }
+    pub fn sell_tokens(&self, chain: &Chain) -> String {
+        format!("{}/execute/sell/v7", self.chain_base(chain))
+    }
+    pub fn mint_tokens(&self, chain: &Chain) -> String {
+        format!("{}/execute/mint/v1", self.chain_base(chain))
+    }
+    pub fn list_tokens(&self, chain: &Chain) -> String {
+        format!("{}/execute/list/v5", self.chain_base(chain))
+    }
+    pub fn place_bids(&self, chain: &Chain) -> String {
+        format!("{}/execute/bid/v5", self.chain_base(chain))
+    }
+    pub fn cancel_orders(&self, chain: &Chain) -> String {
+        format!("{}/execute/cancel/v3", self.chain_base(chain))
+    }
+    /// Url of an endpoint returned within a step, e.g. /execute/status/v1 or /order/v4
+    pub fn step_endpoint(&self, chain: &Chain, endpoint: &str) -> String {
+        format!("{}/{}", self.chain_base(chain), endpoint.trim_start_matches('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_error_status() {
+        let body = r#"{"statusCode":410,"error":"Gone","message":"Order is filled","code":2}"#.to_string();
+        let e = MagicedenApiError::from_status(StatusCode::GONE, body, None);
+        assert!(matches!(e, MagicedenApiError::Gone(_)));
+        assert_eq!(e.status_code(), Some(410));
+        assert!(!e.is_retryable());
+        let api_error = e.api_error().unwrap();
+        assert_eq!(api_error.message(), Some("Order is filled"));
+        assert_eq!(api_error.error.as_ref().unwrap().code, Some(2));
+
+        let body = r#"{"msg":"Invalid params","errors":["limit too large"]}"#.to_string();
+        let e = MagicedenApiError::from_status(StatusCode::BAD_REQUEST, body, None);
+        assert!(matches!(e, MagicedenApiError::BadRequest(_)));
+        assert_eq!(e.api_error().unwrap().error.as_ref().unwrap().errors, vec!["limit too large".to_string()]);
+
+        let e = MagicedenApiError::from_status(StatusCode::TOO_MANY_REQUESTS, String::new(), Some(Duration::from_secs(1)));
+        assert!(matches!(e, MagicedenApiError::RateLimited(_)));
+        assert!(e.is_retryable());
+
+        let e = MagicedenApiError::from_status(StatusCode::BAD_GATEWAY, "<html>".to_string(), None);
+        assert!(matches!(e, MagicedenApiError::ServerError(_)));
+        assert!(e.api_error().unwrap().error.is_none());
+        assert!(e.is_retryable());
+
+        let e = MagicedenApiError::from_status(StatusCode::UNAUTHORIZED, String::new(), None);
+        assert!(matches!(e, MagicedenApiError::Unauthorized(_)));
+        let e = MagicedenApiError::from_status(StatusCode::FORBIDDEN, String::new(), None);
+        assert!(matches!(e, MagicedenApiError::Forbidden(_)));
+        let e = MagicedenApiError::from_status(StatusCode::NOT_FOUND, String::new(), None);
+        assert!(matches!(e, MagicedenApiError::NotFound(_)));
+        let e = MagicedenApiError::from_status(StatusCode::IM_A_TEAPOT, String::new(), None);
+        assert!(matches!(e, MagicedenApiError::UnexpectedStatus(_)));
+    }
+
+    #[test]
+    fn chain_slugs_and_ids() {
+        assert_eq!(Chain::ApeChain.to_string(), "apechain");
+        assert_eq!(Chain::MonadTestnet.to_string(), "monad-testnet");
+        assert_eq!("base-sepolia".parse::<Chain>().unwrap(), Chain::BaseSepolia);
+        assert_eq!(serde_json::to_string(&Chain::Bsc).unwrap(), "\"bsc\"");
+
+        assert_eq!(Chain::from_chain_id(8453), Some(Chain::Base));
+        assert_eq!(Chain::try_from(11155111).unwrap(), Chain::Sepolia);
+        assert!(Chain::from_chain_id(999_999_999).is_none());
+        assert!(matches!(Chain::try_from(999_999_999), Err(MagicedenApiError::InvalidArgument(_))));
+        assert!(Chain::Sepolia.is_test_chain());
+        assert_eq!(Chain::Polygon.native_symbol(), "POL");
+
+        // Chain ids are unique
+        for chain in Chain::iter() {
+            assert_eq!(Chain::from_chain_id(chain.chain_id()), Some(chain));
+        }
+    }
+
+    #[test]
+    fn builds_endpoint_urls() {
+        let url = ApiUrl::new("https://api-mainnet.magiceden.dev/", "v3", "rtp/{chain}");
+        assert_eq!(url.buy_tokens(&Chain::Ethereum), "https://api-mainnet.magiceden.dev/v3/rtp/ethereum/execute/buy/v7");
+        assert_eq!(url.buy_tokens(&Chain::ArbitrumSepolia), "https://api-mainnet.magiceden.dev/v3/rtp/arbitrum-sepolia/execute/buy/v7");
+
+        assert_eq!(url.place_bids(&Chain::Base), "https://api-mainnet.magiceden.dev/v3/rtp/base/execute/bid/v5");
+        assert_eq!(url.mint_tokens(&Chain::Base), "https://api-mainnet.magiceden.dev/v3/rtp/base/execute/mint/v1");
+
+        let url = ApiUrl::new("http://localhost:8080", "", "");
+        assert_eq!(url.retrieve_asks(&Chain::Ethereum, "limit=1".to_string()), "http://localhost:8080/orders/asks/v5?limit=1");
+
+        let url = ApiUrl::new("http://proxy", "v4", "/magiceden/{chain}/");
+        assert_eq!(url.buy_tokens(&Chain::Ethereum), "http://proxy/v4/magiceden/ethereum/execute/buy/v7");
     }
 }
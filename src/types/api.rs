@@ -1,8 +1,15 @@
-use crate::types::MagicedenApiError;
+use crate::types::{
+    primitives::{Address, Bytes, Decimal, B256, U256},
+    MagicedenApiError,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::Duration,
+};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -114,10 +121,10 @@ pub struct BuyTokensRequest {
     // List of items to buy.
     pub items: Vec<Listing>,
     // Address of wallet filling (receiver of the NFT).
-    pub taker: String,
+    pub taker: Address,
     // Address of wallet relaying the fill transaction (paying for the NFT).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub relayer: Option<String>,
+    pub relayer: Option<Address>,
     // If true, only the path will be returned.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub only_path: Option<bool>,
@@ -129,7 +136,7 @@ pub struct BuyTokensRequest {
     pub currency: Option<String>,
     // The chain id of the purchase currency
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub currency_chain_id: Option<u16>,
+    pub currency_chain_id: Option<u64>,
     // Charge any missing royalties.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub normalize_royalties: Option<bool>,
@@ -172,7 +179,7 @@ pub struct BuyTokensRequest {
     pub execution_method: Option<ExecutionProvider>,
     // Referrer address (where supported)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub referrer: Option<String>,
+    pub referrer: Option<Address>,
     // Mint comment (where supported)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
@@ -205,22 +212,31 @@ pub enum Status {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyTokenItemData {
-    pub from: String,
-    pub to: String,
-    pub data: String,
-    pub value: String,
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub value: U256,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Method {
     Post,
+    // Any other method, not supported by the client
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyTokenCheckBody {
     pub kind: BuyTokensKind,
+    // Id of the signature request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // Hash of the sent transaction. Must be set to check the status of a transaction step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -230,61 +246,110 @@ pub struct BuyTokenCheck {
     pub body: BuyTokenCheckBody,
 }
 
+impl BuyTokenCheck {
+    /// Set the hash of the sent transaction to check its status
+    pub fn with_tx_hash(mut self, tx_hash: B256) -> Self {
+        self.body.tx_hash = Some(tx_hash);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecuteStatus {
+    Pending,
+    Received,
+    Success,
+    Failure,
+    #[serde(other)]
+    Unknown,
+}
+
+impl ExecuteStatus {
+    /// True if the step succeeded or failed and will not change anymore
+    pub fn is_final(&self) -> bool {
+        matches!(self, ExecuteStatus::Success | ExecuteStatus::Failure)
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, ExecuteStatus::Success)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BuyTokenItem {
-    pub status: Status,
-    pub tip: Option<String>,
-    pub order_ids: Option<Vec<String>>,
-    pub data: BuyTokenItemData,
-    // Approximation of gas used (only applies to transaction items)
-    pub gas_estimate: u64,
-    // The details of the endpoint for checking the status of the step
-    pub check: BuyTokenCheck,
+pub struct OrderOutcome {
+    pub order_id: Option<String>,
+    pub order_index: Option<usize>,
+    pub status: Option<ExecuteStatus>,
+    pub tx_hash: Option<B256>,
+    pub message: Option<String>,
+}
+
+/// Status of a step returned by the check endpoint
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepStatus {
+    pub status: ExecuteStatus,
+    // Reason of a failure
+    pub details: Option<String>,
+    pub tx_hashes: Option<Vec<B256>>,
+    // Unix timestamp (seconds) of the last status update
+    pub time: Option<u64>,
+    // Outcome of each order of the step, if reported
+    pub orders: Option<Vec<OrderOutcome>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyTokenError {
     pub message: String,
-    pub order_id: String,
+    // Not always an order hash, e.g. `mint:<contract>` for mints, and missing for errors not tied to an order
+    pub order_id: Option<String>,
+}
+
+impl BuyTokenError {
+    /// Mint phase that caused the error, if any
+    pub fn mint_error_kind(&self) -> Option<MintErrorKind> {
+        MintErrorKind::from_message(&self.message)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildInFees {
     pub kind: String,
-    pub recipient: String,
+    pub recipient: Address,
     pub bps: u64,
     pub amount: f64,
-    pub raw_amount: String,
+    pub raw_amount: U256,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyTokenPath {
     pub order_id: String,
-    pub contract: String,
+    pub contract: Address,
     pub token_id: String,
     pub quantity: u16,
     pub source: String,
-    pub currency: String,
+    pub currency: Address,
     pub currency_symbol: String,
     pub currency_decimals: u8,
     pub quote: f64,
-    pub raw_quote: String,
-    pub buy_in_currency: Option<String>,
+    pub raw_quote: U256,
+    pub buy_in_currency: Option<Address>,
     pub buy_in_currency_symbol: Option<String>,
     pub buy_in_currency_decimals: Option<u8>,
     pub buy_in_quote: Option<f64>,
-    pub buy_in_raw_quote: Option<String>,
+    pub buy_in_raw_quote: Option<U256>,
     pub total_price: f64,
-    pub total_raw_price: String,
+    pub total_raw_price: U256,
     // Can be marketplace fees or royalties
     pub built_in_fees: Vec<BuildInFees>,
     // Can be referral fees.
     pub fees_on_top: Vec<BuildInFees>,
-    pub from_chain_id: Option<u16>,
+    pub from_chain_id: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -293,211 +358,1053 @@ pub struct MaxQuantities {
     pub item_index: u16,
     pub max_quantity: String,
 }
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BuyTokensStep {
-    pub id: String,
-    pub action: String,
-    pub description: String,
-    pub kind: BuyTokensKind,
-    pub items: Vec<BuyTokenItem>,
-
-    pub max_quantities: Option<Vec<MaxQuantities>>,
-}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyTokensResponse {
     pub request_id: String,
-    pub steps: Vec<BuyTokensStep>,
+    pub steps: Vec<ExecuteStep>,
     pub errors: Vec<BuyTokenError>,
     pub path: Vec<BuyTokenPath>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub enum SortBy {
-    CreatedAt,
-    UpdatedAt,
-    Price,
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureKind {
+    Eip191,
+    Eip712,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct AsksRequest {
-    pub ids: Option<Vec<String>>,
-    // Filter to a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
-    pub token: Option<String>,
-    // Filter to a particular set, e.g. contract:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
-    pub token_set_id: Option<String>,
-    // Filter to a particular user. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
-    pub maker: Option<String>,
-    // Filter to a particular community. Example: artblocks
-    pub community: Option<String>,
-    // Filter to a particular collection set. Example: 8daa732ebe5db23f267e58d52f1c9b1879279bcdf4f78b8fb563390e6946ea65
-    pub collection_set_id: Option<String>,
-    // Filter to a particular contracts set.
-    pub contract_set_id: Option<String>,
-    pub contracts: Option<Vec<String>>,
-    // activeª^º = currently valid
-    // inactiveª^ = temporarily invalid
-    // expiredª^, cancelledª^, filledª^ = permanently invalid
-    // anyªº = any status
-    // ª when an id is passed
-    // ^ when a maker is passed
-    // º when a contract is passed
-    pub status: Option<String>,
-    pub sources: Option<Vec<String>>,
-    // If true, results will filter only Reservoir orders.
-    pub native: Option<bool>,
-    // If true, private orders are included in the response.
-    pub include_private: Option<bool>,
-    // If true, criteria metadata is included in the response.
-    pub include_criteria_metadata: Option<bool>,
-    // If true, raw data is included in the response.
-    pub include_raw_data: Option<bool>,
-    // If true, dynamic pricing data will be returned in the response.
-    pub include_dynamic_pricing: Option<bool>,
-    // Exclude orders that can only be filled by EOAs, to support filling with smart contracts.
-    #[serde(rename = "excludeEOA")]
-    pub exclude_eoa: Option<bool>,
-    pub exclude_sources: Option<Vec<String>>,
-    // Get events after a particular unix timestamp (inclusive)
-    pub start_timestamp: Option<u64>,
-    // Get events before a particular unix timestamp (inclusive)
-    pub end_timestamp: Option<u64>,
-    // If true, prices will include missing royalties to be added on-top.
-    pub normalize_royalties: Option<bool>,
-    // Order the items are returned in the response. Sorting by price is ascending order only.
-    pub sort_by: Option<SortBy>,
-    pub sort_direction: Option<String>,
-    // Use continuation token to request next offset of items. Going back in time.
-    pub continuation: Option<String>,
-    // Amount of items returned in response. Max limit is 1000.
-    pub limit: Option<u16>,
-    // Return result in given currency
-    pub display_currency: Option<String>,
+pub struct Eip712Domain {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifying_contract: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<B256>,
 }
 
-pub(crate) fn value_to_string(v: &Value) -> Result<String, MagicedenApiError> {
-    match v {
-        Value::Number(n) => Ok(n.to_string()),
-        Value::Bool(b) => Ok(b.to_string()),
-        Value::String(s) => Ok(s.to_owned()),
-        _ => Err(MagicedenApiError::Other(format!("Wrong value type: {v:?}"))),
+impl Eip712Domain {
+    /// Fields of the EIP712Domain type, in the order defined by EIP-712
+    pub fn fields(&self) -> Vec<Eip712Field> {
+        let field = |name: &str, type_: &str| Eip712Field { name: name.to_string(), type_: type_.to_string() };
+        let mut fields = Vec::new();
+        if self.name.is_some() {
+            fields.push(field("name", "string"));
+        }
+        if self.version.is_some() {
+            fields.push(field("version", "string"));
+        }
+        if self.chain_id.is_some() {
+            fields.push(field("chainId", "uint256"));
+        }
+        if self.verifying_contract.is_some() {
+            fields.push(field("verifyingContract", "address"));
+        }
+        if self.salt.is_some() {
+            fields.push(field("salt", "bytes32"));
+        }
+        fields
     }
 }
 
-impl AsksRequest {
-    /// Converts RetrieveListingsRequest into serde_json::Map<String, serde_json::Value>
-    pub fn to_map(&self) -> serde_json::Result<Map<String, Value>> {
-        Ok(serde_json::to_value(self)?.as_object().expect("This should never happen").to_owned())
-    }
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Eip712Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
 
-    /// Converts AsksRequest into a vector of key-value pairs
-    /// Magiceden API expects arrays to be passed as a sequence of parameters with the same key (e.g. ?token_ids=1&token_ids=209)
-    pub fn to_qs_vec(&self) -> Result<Vec<(String, String)>, MagicedenApiError> {
-        let map = self.to_map()?;
-        let mut vec = Vec::new();
-        for (k, v) in map.iter() {
-            match v {
-                Value::Array(arr) => {
-                    for v in arr {
-                        vec.push((k.clone(), value_to_string(v)?))
-                    }
-                }
-                _ => vec.push((k.clone(), value_to_string(v)?)),
-            }
-        }
-        Ok(vec)
+/// Data to sign, either EIP-712 typed data or an EIP-191 message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignData {
+    pub signature_kind: SignatureKind,
+    // EIP-712 domain
+    pub domain: Option<Eip712Domain>,
+    // EIP-712 types without the EIP712Domain type
+    pub types: Option<BTreeMap<String, Vec<Eip712Field>>>,
+    pub primary_type: Option<String>,
+    // EIP-712 message
+    pub value: Option<Value>,
+    // EIP-191 message
+    pub message: Option<String>,
+}
+
+impl SignData {
+    /// Convert EIP-712 sign data into standard typed data as expected by eth_signTypedData_v4. The EIP712Domain type
+    /// is derived from the fields set in the domain.
+    pub fn to_typed_data(&self) -> Result<TypedData, MagicedenApiError> {
+        let (SignatureKind::Eip712, Some(domain), Some(types), Some(primary_type), Some(message)) =
+            (&self.signature_kind, &self.domain, &self.types, &self.primary_type, &self.value)
+        else {
+            return Err(MagicedenApiError::InvalidArgument("Sign data is not complete EIP-712 typed data".to_string()));
+        };
+
+        let mut types = types.clone();
+        types.insert("EIP712Domain".to_string(), domain.fields());
+        Ok(TypedData { types, primary_type: primary_type.clone(), domain: domain.clone(), message: message.clone() })
     }
 }
 
+/// EIP-712 typed data as expected by eth_signTypedData_v4.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum Side {
-    Buy,
-    Sell,
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    // All types including EIP712Domain
+    pub types: BTreeMap<String, Vec<Eip712Field>>,
+    pub primary_type: String,
+    pub domain: Eip712Domain,
+    pub message: Value,
 }
 
+/// Request to send the signature to. The signature is passed as `signature` query parameter.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum OrderStatus {
-    Active,
-    Inactive,
-    Expired,
-    Canceled,
-    Filled,
+pub struct PostData {
+    pub endpoint: String,
+    pub method: Method,
+    pub body: Value,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Currency {
-    pub contract: String,
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
+pub struct SignatureItemData {
+    pub sign: SignData,
+    pub post: Option<PostData>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Amount {
-    pub raw: String,
-    decimal: f64,
-    usd: f64,
-    native: f64,
+/// Data of a step item: a transaction to send or data to sign.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum StepItemData {
+    Transaction(BuyTokenItemData),
+    Signature(Box<SignatureItemData>),
+}
+
+// Untagged enums do not work with the arbitrary_precision feature of serde_json, so we dispatch on the fields
+impl<'de> Deserialize<'de> for StepItemData {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if value.get("sign").is_some() {
+            serde_json::from_value(value).map(|sig| StepItemData::Signature(Box::new(sig))).map_err(serde::de::Error::custom)
+        } else {
+            serde_json::from_value(value).map(StepItemData::Transaction).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+impl StepItemData {
+    pub fn as_transaction(&self) -> Option<&BuyTokenItemData> {
+        match self {
+            StepItemData::Transaction(tx) => Some(tx),
+            StepItemData::Signature(_) => None,
+        }
+    }
+
+    pub fn as_signature(&self) -> Option<&SignatureItemData> {
+        match self {
+            StepItemData::Signature(sig) => Some(sig),
+            StepItemData::Transaction(_) => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Price {
-    pub currency: Currency,
-    pub amount: Amount,
-    pub net_amount: Amount,
+pub struct ExecuteStepItem {
+    pub status: Status,
+    pub tip: Option<String>,
+    pub order_ids: Option<Vec<String>>,
+    // Indexes of the orders of the request this item belongs to
+    pub order_indexes: Option<Vec<usize>>,
+    pub data: Option<StepItemData>,
+    // Approximation of gas used (only applies to transaction items)
+    pub gas_estimate: Option<u64>,
+    // The details of the endpoint for checking the status of the step
+    pub check: Option<BuyTokenCheck>,
+}
+
+/// Id of an execute step. Unknown ids are kept as [`StepId::Other`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum StepId {
+    // Wrap the native currency, e.g. ETH to WETH, to fund a bid
+    CurrencyWrapping,
+    // Approve the exchange to spend the bid currency
+    CurrencyApproval,
+    // Approve the exchange to transfer the NFTs
+    NftApproval,
+    AuthTransaction,
+    OrderSignature,
+    Sale,
+    Cancellation,
+    // Gasless cancellation of orders that support off-chain cancellation
+    CancellationSignature,
+    Other(String),
+}
+
+impl StepId {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StepId::CurrencyWrapping => "currency-wrapping",
+            StepId::CurrencyApproval => "currency-approval",
+            StepId::NftApproval => "nft-approval",
+            StepId::AuthTransaction => "auth-transaction",
+            StepId::OrderSignature => "order-signature",
+            StepId::Sale => "sale",
+            StepId::Cancellation => "cancellation",
+            StepId::CancellationSignature => "cancellation-signature",
+            StepId::Other(id) => id,
+        }
+    }
+}
+
+impl From<String> for StepId {
+    fn from(id: String) -> Self {
+        match id.as_str() {
+            "currency-wrapping" => StepId::CurrencyWrapping,
+            "currency-approval" => StepId::CurrencyApproval,
+            "nft-approval" => StepId::NftApproval,
+            "auth-transaction" => StepId::AuthTransaction,
+            "order-signature" => StepId::OrderSignature,
+            "sale" => StepId::Sale,
+            "cancellation" => StepId::Cancellation,
+            "cancellation-signature" => StepId::CancellationSignature,
+            _ => StepId::Other(id),
+        }
+    }
+}
+
+impl From<StepId> for String {
+    fn from(id: StepId) -> Self {
+        match id {
+            StepId::Other(id) => id,
+            id => id.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for StepId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
+/// Step of an execute response, e.g. an approval transaction or an order signature.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Token {
-    pub token_id: String,
-    pub name: String,
-    pub image: String,
+pub struct ExecuteStep {
+    pub id: StepId,
+    pub action: String,
+    pub description: String,
+    pub kind: BuyTokensKind,
+    pub items: Vec<ExecuteStepItem>,
+    // Mintable quantity per item, only set for mints
+    pub max_quantities: Option<Vec<MaxQuantities>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Collection {
-    pub id: String,
-    pub name: String,
-    pub image: String,
+#[serde(rename_all = "kebab-case")]
+pub enum Orderbook {
+    Reservoir,
+    Opensea,
+    LooksRare,
+    X2y2,
+    Blur,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct CriteriaData {
-    pub token: Option<Token>,
-    pub collection: Option<Collection>,
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingParams {
+    // Token to list. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: String,
+    // Quantity of tokens to list. Only relevant for ERC1155.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    // Amount seller is willing to sell for in the smallest denomination of the currency. Example: 1000000000000000000
+    pub wei_price: U256,
+    // Exchange protocol used to create the order. Example: seaport-v1.6
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_kind: Option<RawOrderKind>,
+    // Orderbook where the listing will be posted. Default: reservoir
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orderbook: Option<Orderbook>,
+    // Optional API key for the target orderbook
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orderbook_api_key: Option<String>,
+    // If true, royalty amounts and recipients will be set automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automated_royalties: Option<bool>,
+    // Set a maximum amount of royalties to pay, rather than the full amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_bps: Option<u32>,
+    // List of fees (formatted as feeRecipient:feeBps) to be bundled within the order.
+    // Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00:100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fees: Option<Vec<String>>,
+    // Unix timestamp (seconds) indicating when the listing will be listed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listing_time: Option<String>,
+    // Unix timestamp (seconds) indicating when the listing will expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_time: Option<String>,
+    // Optional. Random string to make the order unique
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    // Optional. Set a custom nonce
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    // Currency to list in. Defaults to the native currency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Criteria {
-    pub kind: String,
-    pub data: Amount,
-    pub net_amount: CriteriaData,
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTokensRequest {
+    // Address of wallet making the order.
+    pub maker: Address,
+    // Domain of your app that is creating the order, e.g. myapp.xyz
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // List of listings to create.
+    pub params: Vec<ListingParams>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct FeeBreakdown {
-    // Can be marketplace or royalty
-    pub kind: String,
-    pub recipient: String,
-    pub bps: u64,
+#[serde(rename_all = "camelCase")]
+pub struct OrderError {
+    pub message: String,
+    // Index of the failed order in the request
+    pub order_index: Option<usize>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Depth {
-    pub price: u64,
-    pub quantity: u64,
+#[serde(rename_all = "camelCase")]
+pub struct ListTokensResponse {
+    pub steps: Vec<ExecuteStep>,
+    pub errors: Option<Vec<OrderError>>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum Kind {
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BidParams {
+    // Bid on a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    // Bid on a particular token set. Example: list:CONTRACT:TOKEN_IDS_HASH
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_set_id: Option<String>,
+    // Bid on a whole collection, or on an attribute of it if attribute_key and attribute_value are set.
+    // Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    // Attribute key of an attribute bid. Example: Background
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute_key: Option<String>,
+    // Attribute value of an attribute bid. Example: Blue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute_value: Option<String>,
+    // Quantity of tokens to bid on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    // Amount bidder is willing to offer in the smallest denomination of the currency. Example: 1000000000000000000
+    pub wei_price: U256,
+    // Exchange protocol used to create the order. Example: seaport-v1.6
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_kind: Option<RawOrderKind>,
+    // Orderbook where the bid will be posted. Default: reservoir
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orderbook: Option<Orderbook>,
+    // Optional API key for the target orderbook
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orderbook_api_key: Option<String>,
+    // If true, royalty amounts and recipients will be set automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automated_royalties: Option<bool>,
+    // Set a maximum amount of royalties to pay, rather than the full amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_bps: Option<u32>,
+    // List of fees (formatted as feeRecipient:feeBps) to be bundled within the order.
+    // Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00:100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fees: Option<Vec<String>>,
+    // If true, flagged tokens will be excluded from collection and attribute bids.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_flagged_tokens: Option<bool>,
+    // Unix timestamp (seconds) indicating when the bid will be listed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listing_time: Option<String>,
+    // Unix timestamp (seconds) indicating when the bid will expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_time: Option<String>,
+    // Optional. Random string to make the order unique
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    // Optional. Set a custom nonce
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    // Currency to bid in. Defaults to the wrapped native currency, e.g. WETH.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    // If true, a permit is used instead of an approval transaction, if the currency supports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_permit: Option<bool>,
+}
+
+impl BidParams {
+    /// Bid on a single token
+    pub fn token(token: impl Into<String>, wei_price: U256) -> Self {
+        Self { token: Some(token.into()), wei_price, ..Default::default() }
+    }
+
+    /// Bid on any token of a collection
+    pub fn collection(collection: impl Into<String>, wei_price: U256) -> Self {
+        Self { collection: Some(collection.into()), wei_price, ..Default::default() }
+    }
+
+    /// Bid on any token of a collection with the given attribute
+    pub fn attribute(collection: impl Into<String>, key: impl Into<String>, value: impl Into<String>, wei_price: U256) -> Self {
+        Self {
+            collection: Some(collection.into()),
+            attribute_key: Some(key.into()),
+            attribute_value: Some(value.into()),
+            wei_price,
+            ..Default::default()
+        }
+    }
+
+    /// Bid on any token of a token set
+    pub fn token_set(token_set_id: impl Into<String>, wei_price: U256) -> Self {
+        Self { token_set_id: Some(token_set_id.into()), wei_price, ..Default::default() }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BidRequest {
+    // Address of wallet making the order.
+    pub maker: Address,
+    // Domain of your app that is creating the order, e.g. myapp.xyz
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // List of bids to create.
+    pub params: Vec<BidParams>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceBidsResponse {
+    // Wrapping, currency approval and signature steps to post the bids
+    pub steps: Vec<ExecuteStep>,
+    pub errors: Option<Vec<OrderError>>,
+}
+
+impl PlaceBidsResponse {
+    /// Returns the step with the given id, e.g. [`StepId::CurrencyWrapping`]
+    pub fn step(&self, id: &StepId) -> Option<&ExecuteStep> {
+        self.steps.iter().find(|step| &step.id == id)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MintItem {
+    // Collection to mint. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    // Token to mint, e.g. of an ERC1155 open edition. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    // Quantity of tokens to mint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MintRequest {
+    // List of items to mint.
+    pub items: Vec<MintItem>,
+    // Address of wallet minting.
+    pub taker: Address,
+    // Address of wallet relaying the mint transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relayer: Option<Address>,
+    // Minting source used for attribution. Example: magiceden.io
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // Referrer address, if the mint supports referral rewards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<Address>,
+    // Mint comment, if the mint supports comments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    // Currency to pay the mint in. Defaults to the currency of the mint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    // If true, any mintable quantity is minted, even if less than requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<bool>,
+    // If true, balance check will be skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_balance_check: Option<bool>,
+    // Optional custom gas settings. Includes base fee & priority fee in this limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<String>,
+    // Optional custom gas settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+/// Reason a mint is not possible in its current phase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintErrorKind {
+    NotStarted,
+    Ended,
+    SoldOut,
+    MaxPerWallet,
+    // The taker is not on the allowlist of the current phase
+    NotEligible,
+}
+
+// Whole phrases of mint phase errors, matched on word boundaries. Reason codes like `not-yet-started` match too.
+const MINT_ERROR_PHRASES: &[(MintErrorKind, &[&str])] = &[
+    (MintErrorKind::NotStarted, &["not yet started", "has not started", "not started yet", "mint not started", "not yet live"]),
+    (MintErrorKind::Ended, &["mint has ended", "mint ended", "sale has ended", "mint is over", "mint is closed"]),
+    (MintErrorKind::SoldOut, &["sold out", "max supply exceeded", "max supply reached", "exceeds max supply", "not enough supply"]),
+    (MintErrorKind::MaxPerWallet, &["per wallet", "wallet limit reached"]),
+    (MintErrorKind::NotEligible, &["not eligible", "no eligible mints", "not on the allowlist", "not allowlisted"]),
+];
+
+impl MintErrorKind {
+    /// Classify the error message of a failed mint. `None` if the error is not caused by the mint phase, e.g. an
+    /// insufficient balance or invalid parameters.
+    pub fn from_message(message: &str) -> Option<Self> {
+        let words: Vec<String> = message.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_lowercase).collect();
+        let message = format!(" {} ", words.join(" "));
+        MINT_ERROR_PHRASES
+            .iter()
+            .find(|(_, phrases)| phrases.iter().any(|phrase| message.contains(&format!(" {phrase} "))))
+            .map(|(kind, _)| *kind)
+    }
+}
+
+/// Error of a mint that is not possible in its current phase
+#[derive(Error, Clone, Debug)]
+#[error("{message}")]
+pub struct MintError {
+    // Kind of the first error caused by the mint phase
+    pub kind: MintErrorKind,
+    pub message: String,
+    // All errors of the response, including those not caused by the mint phase
+    pub errors: Vec<BuyTokenError>,
+}
+
+impl MintError {
+    /// Mint error of the first error caused by the mint phase, if any
+    pub fn from_errors(errors: Vec<BuyTokenError>) -> Option<Self> {
+        let (kind, message) = errors.iter().find_map(|error| Some((error.mint_error_kind()?, error.message.clone())))?;
+        Some(Self { kind, message, errors })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintPath {
+    pub order_id: Option<String>,
+    pub contract: Address,
+    pub token_id: Option<String>,
+    pub quantity: Option<u64>,
+    pub source: Option<String>,
+    pub currency: Option<Address>,
+    pub currency_symbol: Option<String>,
+    pub currency_decimals: Option<u8>,
+    // Price per token in the currency of the mint
+    pub quote: Option<Decimal>,
+    pub raw_quote: Option<U256>,
+    pub total_price: Option<Decimal>,
+    pub total_raw_price: Option<U256>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintTokensResponse {
+    pub request_id: Option<String>,
+    // Steps to execute the mint. maxQuantities of a step holds the mintable quantity per item.
+    pub steps: Vec<ExecuteStep>,
+    #[serde(default)]
+    pub errors: Vec<BuyTokenError>,
+    #[serde(default)]
+    pub path: Vec<MintPath>,
+}
+
+impl MintTokensResponse {
+    /// Maximum quantity that can be minted per item index
+    pub fn max_quantities(&self) -> impl Iterator<Item = &MaxQuantities> {
+        self.steps.iter().filter_map(|step| step.max_quantities.as_ref()).flatten()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Bid {
+    // Token to sell. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: String,
+    // Quantity of tokens to sell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u16>,
+    // Optional order id to sell into.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    // Optional raw order to sell into.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_order: Option<RawOrder>,
+    // Only consider orders from this source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact_order_source: Option<String>,
+    // Items to exclude
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusions: Option<Vec<ExcludeItem>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SellTokensRequest {
+    // List of items to sell.
+    pub items: Vec<Bid>,
+    // Address of wallet filling.
+    pub taker: Address,
+    // Address of wallet relaying the fill transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relayer: Option<Address>,
+    // Filling source used for attribution. Example: magiceden.io
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    // List of fees (formatted as feeRecipient:feeAmount) to be taken when filling.
+    // Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00:1000000000000000
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fees: Option<Vec<String>>,
+    // If true, only the path will be returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only_path: Option<bool>,
+    // Charge any missing royalties.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalize_royalties: Option<bool>,
+    // If true, inactive orders will not be skipped over (only relevant when filling via a specific order id).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_inactive_order_ids: Option<bool>,
+    // If true, any off-chain or on-chain errors will be skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<bool>,
+    // If true, all fills will be executed through the router (where possible)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_router: Option<bool>,
+    // Currency to receive the proceeds in, swapped if it differs from the currency of the bid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    // Exclude orders that can only be filled by EOAs, to support filling with smart contracts.
+    #[serde(rename = "excludeEOA")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_eoa: Option<bool>,
+    // Optional custom gas settings. Includes base fee & priority fee in this limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<String>,
+    // Optional custom gas settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+    // Choose a specific swapping provider when selling into a different currency (defaults to uniswap)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap_provider: Option<SwapProvider>,
+    // Optional X2Y2 API key used for filling.
+    #[serde(rename = "x2y2ApiKey")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x2y2_api_key: Option<String>,
+    // Optional OpenSea API key used for filling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opensea_api_key: Option<String>,
+    // Advanced use case to pass personal blurAuthToken; the API will generate one if left empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blur_auth_token: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellTokenPath {
+    pub order_id: String,
+    pub contract: Address,
+    pub token_id: String,
+    pub quantity: u16,
+    pub source: Option<String>,
+    pub currency: Address,
+    pub currency_symbol: String,
+    pub currency_decimals: u8,
+    pub quote: f64,
+    pub raw_quote: U256,
+    pub sell_out_currency: Option<Address>,
+    pub sell_out_currency_symbol: Option<String>,
+    pub sell_out_currency_decimals: Option<u8>,
+    pub sell_out_quote: Option<f64>,
+    pub sell_out_raw_quote: Option<U256>,
+    pub total_price: Option<f64>,
+    pub total_raw_price: Option<U256>,
+    // Can be marketplace fees or royalties
+    pub built_in_fees: Vec<BuildInFees>,
+    // Can be referral fees.
+    pub fees_on_top: Vec<BuildInFees>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellTokensResponse {
+    pub request_id: Option<String>,
+    pub steps: Vec<ExecuteStep>,
+    #[serde(default)]
+    pub errors: Vec<BuyTokenError>,
+    #[serde(default)]
+    pub path: Vec<SellTokenPath>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelRequest {
+    // Ids of the orders to cancel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_ids: Option<Vec<String>>,
+    // Cancel all orders of this maker. Requires an order kind.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maker: Option<Address>,
+    // Order kind of a bulk cancel. Example: seaport-v1.6
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_kind: Option<RawOrderKind>,
+    // Only cancel the orders of this token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    // Optional. Set custom gas price for the cancellation transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<String>,
+    // Optional. Set custom gas price for the cancellation transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+impl CancelRequest {
+    /// Cancel the given orders. Orders that are natively off-chain cancellable are cancelled by a signature.
+    pub fn orders<I, S>(order_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self { order_ids: Some(order_ids.into_iter().map(Into::into).collect()), ..Default::default() }
+    }
+
+    /// Cancel all orders of `maker` with the given order kind
+    pub fn bulk(maker: Address, order_kind: RawOrderKind) -> Self {
+        Self { maker: Some(maker), order_kind: Some(order_kind), ..Default::default() }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrdersResponse {
+    // Either a cancellation signature step or a cancellation transaction step
+    pub steps: Vec<ExecuteStep>,
+}
+
+impl CancelOrdersResponse {
+    /// True if the orders are cancelled by a signature instead of a transaction. False if there is nothing to cancel.
+    pub fn is_off_chain(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|step| step.kind == BuyTokensKind::Signature)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    CreatedAt,
+    UpdatedAt,
+    Price,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AsksRequest {
+    pub ids: Option<Vec<String>>,
+    // Filter to a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: Option<String>,
+    // Filter to a particular set, e.g. contract:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub token_set_id: Option<String>,
+    // Filter to a particular user. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
+    pub maker: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to a particular collection set. Example: 8daa732ebe5db23f267e58d52f1c9b1879279bcdf4f78b8fb563390e6946ea65
+    pub collection_set_id: Option<String>,
+    // Filter to a particular contracts set.
+    pub contract_set_id: Option<String>,
+    pub contracts: Option<Vec<String>>,
+    // activeª^º = currently valid
+    // inactiveª^ = temporarily invalid
+    // expiredª^, cancelledª^, filledª^ = permanently invalid
+    // anyªº = any status
+    // ª when an id is passed
+    // ^ when a maker is passed
+    // º when a contract is passed
+    pub status: Option<String>,
+    pub sources: Option<Vec<String>>,
+    // If true, results will filter only Reservoir orders.
+    pub native: Option<bool>,
+    // If true, private orders are included in the response.
+    pub include_private: Option<bool>,
+    // If true, criteria metadata is included in the response.
+    pub include_criteria_metadata: Option<bool>,
+    // If true, raw data is included in the response.
+    pub include_raw_data: Option<bool>,
+    // If true, dynamic pricing data will be returned in the response.
+    pub include_dynamic_pricing: Option<bool>,
+    // Exclude orders that can only be filled by EOAs, to support filling with smart contracts.
+    #[serde(rename = "excludeEOA")]
+    pub exclude_eoa: Option<bool>,
+    pub exclude_sources: Option<Vec<String>>,
+    // Get events after a particular unix timestamp (inclusive)
+    pub start_timestamp: Option<u64>,
+    // Get events before a particular unix timestamp (inclusive)
+    pub end_timestamp: Option<u64>,
+    // If true, prices will include missing royalties to be added on-top.
+    pub normalize_royalties: Option<bool>,
+    // Order the items are returned in the response. Sorting by price is ascending order only.
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<String>,
+    // Use continuation token to request next offset of items. Going back in time.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 1000.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+pub(crate) fn value_to_string(v: &Value) -> Result<String, MagicedenApiError> {
+    match v {
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::String(s) => Ok(s.to_owned()),
+        _ => Err(MagicedenApiError::Other(format!("Wrong value type: {v:?}"))),
+    }
+}
+
+impl AsksRequest {
+    /// Converts RetrieveListingsRequest into serde_json::Map<String, serde_json::Value>
+    pub fn to_map(&self) -> serde_json::Result<Map<String, Value>> {
+        Ok(serde_json::to_value(self)?.as_object().expect("This should never happen").to_owned())
+    }
+
+    /// Converts AsksRequest into a vector of key-value pairs
+    /// Magiceden API expects arrays to be passed as a sequence of parameters with the same key (e.g. ?token_ids=1&token_ids=209)
+    pub fn to_qs_vec(&self) -> Result<Vec<(String, String)>, MagicedenApiError> {
+        let map = self.to_map()?;
+        let mut vec = Vec::new();
+        for (k, v) in map.iter() {
+            match v {
+                Value::Array(arr) => {
+                    for v in arr {
+                        vec.push((k.clone(), value_to_string(v)?))
+                    }
+                }
+                _ => vec.push((k.clone(), value_to_string(v)?)),
+            }
+        }
+        Ok(vec)
+    }
+}
+
+/// Serializes `params` as query string and appends the attribute filters as `key[name]=value` pairs.
+pub(crate) fn query_with_attributes<T: Serialize>(
+    params: &T,
+    key: &str,
+    attributes: &[(String, String)],
+) -> Result<String, MagicedenApiError> {
+    let mut query = serde_url_params::to_string(params)?;
+    if !attributes.is_empty() {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for (name, value) in attributes {
+            serializer.append_pair(&format!("{key}[{name}]"), value);
+        }
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(&serializer.finish());
+    }
+    Ok(query)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BidsRequest {
+    pub ids: Option<Vec<String>>,
+    // Filter to a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: Option<String>,
+    // Filter to a particular set. Example: token:CONTRACT:TOKEN_ID representing a single token within contract,
+    // contract:CONTRACT representing a whole contract, range:CONTRACT:START_TOKEN_ID:END_TOKEN_ID representing a
+    // continuous token id range within a contract and list:CONTRACT:TOKEN_IDS_HASH representing a list of token ids
+    // within a contract.
+    pub token_set_id: Option<String>,
+    // Filter to a particular user. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
+    pub maker: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to a particular collection set. Example: 8daa732ebe5db23f267e58d52f1c9b1879279bcdf4f78b8fb563390e6946ea65
+    pub collection_set_id: Option<String>,
+    // Filter to a particular contracts set.
+    pub contract_set_id: Option<String>,
+    // Filter to a particular collection bids with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular attribute, passed as attribute[key]=value. Requires a collection.
+    #[serde(skip)]
+    pub attributes: Vec<(String, String)>,
+    pub contracts: Option<Vec<String>>,
+    // activeª^º = currently valid
+    // inactiveª^ = temporarily invalid
+    // expiredª^, cancelledª^, filledª^ = permanently invalid
+    // anyªº = any status
+    // ª when an id is passed
+    // ^ when a maker is passed
+    // º when a contract is passed
+    pub status: Option<String>,
+    pub sources: Option<Vec<String>>,
+    // If true, results will filter only Reservoir orders.
+    pub native: Option<bool>,
+    // If true, private orders are included in the response.
+    pub include_private: Option<bool>,
+    // If true, criteria metadata is included in the response.
+    pub include_criteria_metadata: Option<bool>,
+    // If true, raw data is included in the response.
+    pub include_raw_data: Option<bool>,
+    // If true, the depth of each order is included in the response.
+    pub include_depth: Option<bool>,
+    // Exclude orders that can only be filled by EOAs, to support filling with smart contracts.
+    #[serde(rename = "excludeEOA")]
+    pub exclude_eoa: Option<bool>,
+    pub exclude_sources: Option<Vec<String>>,
+    // Get events after a particular unix timestamp (inclusive)
+    pub start_timestamp: Option<u64>,
+    // Get events before a particular unix timestamp (inclusive)
+    pub end_timestamp: Option<u64>,
+    // If true, prices will include missing royalties to be added on-top.
+    pub normalize_royalties: Option<bool>,
+    // Order the items are returned in the response. Sorting by price defaults sorting direction to descending.
+    pub sort_by: Option<SortBy>,
+    pub sort_direction: Option<String>,
+    // Use continuation token to request next offset of items. Going back in time.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 1000.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+impl BidsRequest {
+    /// Query string of the request including the attribute filters
+    pub fn to_query_string(&self) -> Result<String, MagicedenApiError> {
+        query_with_attributes(self, "attribute", &self.attributes)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Active,
+    Inactive,
+    Expired,
+    Canceled,
+    Filled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Currency {
+    pub contract: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Amount {
+    // Amount in the smallest unit of the currency
+    pub raw: U256,
+    // Amount in the currency, e.g. 0.049 ETH
+    pub decimal: f64,
+    pub usd: f64,
+    // Amount in the native currency of the chain
+    pub native: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Price {
+    pub currency: Currency,
+    pub amount: Amount,
+    pub net_amount: Amount,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Token {
+    pub token_id: String,
+    pub name: String,
+    pub image: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CriteriaData {
+    pub token: Option<Token>,
+    pub collection: Option<Collection>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Criteria {
+    pub kind: String,
+    pub data: Amount,
+    pub net_amount: CriteriaData,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    // Can be marketplace or royalty
+    pub kind: String,
+    pub recipient: Address,
+    pub bps: u64,
+}
+
+/// Price level of the order depth
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Depth {
+    // Price in the native currency, e.g. 0.0123
+    pub price: Decimal,
+    // Quantity available at this price. Fractional for pool orders, e.g. nftx.
+    pub quantity: Decimal,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Kind {
     #[serde(rename = "blur")]
     Blur,
     #[serde(rename = "seaport-v1.4")]
@@ -542,80 +1449,1189 @@ pub enum Kind {
     ZeroExV4Erc1155,
     #[serde(rename = "mintify")]
     Mintify,
+    #[serde(rename = "mint")]
+    Mint,
+    // Any kind not known to this client
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub id: String,
+    pub kind: Kind,
+    pub side: Side,
+    pub status: OrderStatus,
+    pub token_set_id: String,
+    pub token_set_schema_hash: B256,
+    pub contract: Option<Address>,
+    pub contract_kind: Option<String>,
+    pub maker: Address,
+    pub taker: Address,
+    pub price: Option<Price>,
+    pub valid_from: u64,
+    pub valid_until: u64,
+    pub quantity_filled: Option<u64>,
+    pub quantity_remaining: Option<u64>,
+    //pub dynamic_pricing: Option<DynamicPricing>
+    pub critera: Option<Criteria>,
+    pub source: Option<HashMap<String, String>>,
+    pub fee_bps: Option<u64>,
+    pub fee_breakdown: Vec<FeeBreakdown>,
+    pub expiration: u64,
+    pub is_reservoir: Option<bool>,
+    pub is_dynamic: Option<bool>,
+    // Time when added to indexer
+    pub created_at: DateTime<Utc>,
+    // Time when updated in indexer
+    pub updated_at: DateTime<Utc>,
+    // Time when created by maker
+    pub originated_at: Option<DateTime<Utc>>,
+    pub raw_data: Option<HashMap<String, String>>,
+    pub is_native_off_chain_cancellable: Option<bool>,
+    pub depth: Option<Vec<Depth>>,
+}
+
+impl Order {
+    /// True if the order can be cancelled by a signature without paying gas
+    pub fn is_off_chain_cancellable(&self) -> bool {
+        self.is_native_off_chain_cancellable.unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsksResponse {
+    pub orders: Vec<Order>,
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BidsResponse {
+    pub orders: Vec<Order>,
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokensSortBy {
+    FloorAskPrice,
+    TokenId,
+    Rarity,
+    ListedAt,
+    UpdatedAt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokensRequest {
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to particular contracts. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub contract: Option<Vec<String>>,
+    // Filter to particular tokens. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub tokens: Option<Vec<String>>,
+    // Filter to a particular token set. Example: token:0xa7d8d9ef8d8ce8992df33d8b8cf4aebabd5bd270:129000685
+    pub token_set_id: Option<String>,
+    // Filter to a particular attribute, passed as attributes[key]=value. Requires a collection.
+    #[serde(skip)]
+    pub attributes: Vec<(String, String)>,
+    // Filter to tokens of a particular owner. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
+    pub owner: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to a particular collection set.
+    pub collections_set_id: Option<String>,
+    // Filter to tokens with a rarity rank of at least this value (1 is the rarest)
+    pub min_rarity_rank: Option<u64>,
+    // Filter to tokens with a rarity rank of at most this value
+    pub max_rarity_rank: Option<u64>,
+    // Filter to tokens with a floor ask of at least this price, in the native currency. Example: 0.5
+    pub min_floor_ask_price: Option<f64>,
+    // Filter to tokens with a floor ask of at most this price, in the native currency.
+    pub max_floor_ask_price: Option<f64>,
+    // Filter to tokens listed on a particular source. Example: magiceden.io
+    pub source: Option<String>,
+    // Order the items are returned in the response. Defaults to floorAskPrice.
+    pub sort_by: Option<TokensSortBy>,
+    pub sort_direction: Option<String>,
+    // If true, the top bid is included in the response.
+    pub include_top_bid: Option<bool>,
+    // If true, the attributes are included in the response.
+    pub include_attributes: Option<bool>,
+    // If true, the last sale is included in the response.
+    pub include_last_sale: Option<bool>,
+    // If true, prices will include missing royalties to be added on-top.
+    pub normalize_royalties: Option<bool>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 100.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+impl TokensRequest {
+    /// Query string of the request including the attribute filters
+    pub fn to_query_string(&self) -> Result<String, MagicedenApiError> {
+        query_with_attributes(self, "attributes", &self.attributes)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderSource {
+    pub id: Option<String>,
+    pub domain: Option<String>,
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Floor ask or top bid of a token
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketOrder {
+    pub id: Option<String>,
+    pub price: Option<Price>,
+    pub maker: Option<Address>,
+    pub valid_from: Option<u64>,
+    pub valid_until: Option<u64>,
+    pub quantity_filled: Option<u64>,
+    pub quantity_remaining: Option<u64>,
+    pub source: Option<OrderSource>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMarket {
+    pub floor_ask: Option<MarketOrder>,
+    pub top_bid: Option<MarketOrder>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSale {
+    pub order_source: Option<String>,
+    pub fill_source: Option<String>,
+    // Unix timestamp (seconds) of the sale
+    pub timestamp: Option<u64>,
+    pub price: Option<Price>,
+    pub marketplace_fee_bps: Option<u64>,
+    pub royalty_fee_bps: Option<u64>,
+    pub paid_full_royalty: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAttribute {
+    pub key: String,
+    pub kind: Option<String>,
+    pub value: String,
+    pub token_count: Option<u64>,
+    pub on_sale_count: Option<u64>,
+    pub floor_ask_price: Option<Price>,
+    pub top_bid_value: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCollection {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(alias = "imageUrl")]
+    pub image: Option<String>,
+    pub slug: Option<String>,
+    pub symbol: Option<String>,
+    pub creator: Option<Address>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub token_count: Option<u64>,
+    // Only returned by the user tokens endpoint
+    pub floor_ask_price: Option<Price>,
+    pub royalties_bps: Option<u64>,
+}
+
+/// Token metadata as returned by the tokens endpoint
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDetails {
+    pub chain_id: Option<u64>,
+    pub contract: Address,
+    pub token_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub image_small: Option<String>,
+    pub image_large: Option<String>,
+    pub metadata: Option<Value>,
+    pub media: Option<String>,
+    // erc721 or erc1155
+    pub kind: Option<String>,
+    pub is_flagged: Option<bool>,
+    pub is_spam: Option<bool>,
+    pub supply: Option<String>,
+    pub remaining_supply: Option<String>,
+    // Rarity score, higher is rarer
+    #[serde(alias = "rarityScore")]
+    pub rarity: Option<f64>,
+    // Rarity rank within the collection, 1 is the rarest
+    pub rarity_rank: Option<u64>,
+    pub collection: Option<TokenCollection>,
+    pub last_sale: Option<LastSale>,
+    pub owner: Option<Address>,
+    pub attributes: Option<Vec<TokenAttribute>>,
+    // Top bid of the token. Only returned by the user tokens endpoint, see TokenMarket otherwise.
+    pub top_bid: Option<MarketOrder>,
+    // Appraised value in the native currency. Only returned by the user tokens endpoint.
+    pub last_appraisal_value: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenWithMarket {
+    pub token: TokenDetails,
+    pub market: Option<TokenMarket>,
+    pub updated_at: Option<String>,
+}
+
+impl TokenWithMarket {
+    /// Price of the floor ask, if the token is listed
+    pub fn floor_ask_price(&self) -> Option<&Price> {
+        self.market.as_ref()?.floor_ask.as_ref()?.price.as_ref()
+    }
+
+    /// Price of the top bid, if the token has a bid and the top bid was requested
+    pub fn top_bid_price(&self) -> Option<&Price> {
+        self.market.as_ref()?.top_bid.as_ref()?.price.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TokensResponse {
+    pub tokens: Vec<TokenWithMarket>,
+    pub continuation: Option<String>,
+}
+
+/// Deserializes a count the API returns either as number or as string, e.g. tokenCount.
+pub(crate) fn deserialize_count<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) => n.as_u64().map(Some).ok_or_else(|| serde::de::Error::custom(format!("invalid count: {n}"))),
+        Some(Value::String(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+        Some(v) => Err(serde::de::Error::custom(format!("invalid count: {v}"))),
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CollectionsSortBy {
+    #[serde(rename = "1DayVolume")]
+    OneDayVolume,
+    #[serde(rename = "7DayVolume")]
+    SevenDayVolume,
+    #[serde(rename = "30DayVolume")]
+    ThirtyDayVolume,
+    #[serde(rename = "allTimeVolume")]
+    AllTimeVolume,
+    #[serde(rename = "createdAt")]
+    CreatedAt,
+    #[serde(rename = "updatedAt")]
+    UpdatedAt,
+    #[serde(rename = "floorAskPrice")]
+    FloorAskPrice,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionsRequest {
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub id: Option<String>,
+    // Filter to a particular collection slug. Example: boredapeyachtclub
+    pub slug: Option<String>,
+    // Filter to particular contracts. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub contract: Option<Vec<String>>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to a particular collection set. Example: 8daa732ebe5db23f267e58d52f1c9b1879279bcdf4f78b8fb563390e6946ea65
+    pub collections_set_id: Option<String>,
+    // Filter to a particular creator. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
+    pub creator: Option<String>,
+    // Search for collections that match a string. Example: bored
+    pub name: Option<String>,
+    // Filter to collections with a floor ask of at least this price, in the native currency.
+    pub min_floor_ask_price: Option<f64>,
+    // Filter to collections with a floor ask of at most this price, in the native currency.
+    pub max_floor_ask_price: Option<f64>,
+    // If true, the top bid is included in the response.
+    pub include_top_bid: Option<bool>,
+    // If true, prices will include missing royalties to be added on-top.
+    pub normalize_royalties: Option<bool>,
+    // If true, the floor ask ignores flagged tokens.
+    pub use_non_flagged_floor_ask: Option<bool>,
+    // Order the items are returned in the response. Defaults to allTimeVolume.
+    pub sort_by: Option<CollectionsSortBy>,
+    pub sort_direction: Option<String>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 20.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+/// Values of the 1 day, 7 day, 30 day and all time windows, e.g. volume or rank.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TimeWindows<T> {
+    #[serde(rename = "1day")]
+    pub one_day: Option<T>,
+    #[serde(rename = "7day")]
+    pub seven_day: Option<T>,
+    #[serde(rename = "30day")]
+    pub thirty_day: Option<T>,
+    #[serde(rename = "allTime")]
+    pub all_time: Option<T>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Fee {
+    pub bps: u64,
+    pub recipient: Address,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Royalties {
+    pub recipient: Option<Address>,
+    pub bps: Option<u64>,
+    pub breakdown: Option<Vec<Fee>>,
+}
+
+/// Floor ask or top bid of a collection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionOrder {
+    pub id: Option<String>,
+    pub source_domain: Option<String>,
+    pub price: Option<Price>,
+    pub maker: Option<Address>,
+    pub valid_from: Option<u64>,
+    pub valid_until: Option<u64>,
+    // Token of the floor ask
+    pub token: Option<TokenDetails>,
+}
+
+/// Collection with its stats as returned by the collections endpoint
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDetails {
+    pub chain_id: Option<u64>,
+    pub id: String,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub banner: Option<String>,
+    pub description: Option<String>,
+    pub external_url: Option<String>,
+    pub twitter_username: Option<String>,
+    pub is_spam: Option<bool>,
+    pub primary_contract: Option<Address>,
+    pub token_set_id: Option<String>,
+    pub creator: Option<Address>,
+    pub contract_kind: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub token_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub on_sale_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub owner_count: Option<u64>,
+    pub floor_ask: Option<CollectionOrder>,
+    pub top_bid: Option<CollectionOrder>,
+    pub rank: Option<TimeWindows<u64>>,
+    // Volume in the native currency
+    pub volume: Option<TimeWindows<f64>>,
+    // Volume relative to the previous window, e.g. 1.2 is an increase of 20%
+    pub volume_change: Option<TimeWindows<f64>>,
+    // Floor sale price at the start of the window
+    pub floor_sale: Option<TimeWindows<f64>>,
+    // Current floor relative to the floor sale of the window
+    pub floor_sale_change: Option<TimeWindows<f64>>,
+    pub royalties: Option<Royalties>,
+    // Royalties by royalty standard, e.g. eip2981 or onchain
+    pub all_royalties: Option<HashMap<String, Vec<Fee>>>,
+    pub marketplace_fees: Option<Vec<Fee>>,
+    pub collection_bid_supported: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollectionsResponse {
+    pub collections: Vec<CollectionDetails>,
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UserTokensSortBy {
+    AcquiredAt,
+    LastAppraisalValue,
+    FloorAskPrice,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTokensRequest {
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular contract. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub contract: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to a particular collection set.
+    pub collections_set_id: Option<String>,
+    // Filter to particular tokens. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub tokens: Option<Vec<String>>,
+    // If true, only tokens listed by the user are returned.
+    pub only_listed: Option<bool>,
+    // If true, tokens of spam collections are excluded.
+    pub exclude_spam: Option<bool>,
+    // If true, the top bid is included in the response.
+    pub include_top_bid: Option<bool>,
+    // If true, the attributes are included in the response.
+    pub include_attributes: Option<bool>,
+    // If true, the last sale is included in the response.
+    pub include_last_sale: Option<bool>,
+    // If true, prices will include missing royalties to be added on-top.
+    pub normalize_royalties: Option<bool>,
+    // Order the items are returned in the response. Defaults to acquiredAt.
+    pub sort_by: Option<UserTokensSortBy>,
+    pub sort_direction: Option<String>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 200.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+/// Ownership of a token by the requested user
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+    // Quantity owned. More than 1 only for ERC1155.
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub token_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub on_sale_count: Option<u64>,
+    // Lowest listing of the user for this token
+    pub floor_ask: Option<MarketOrder>,
+    // Time when the user acquired the token
+    pub acquired_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserToken {
+    pub token: TokenDetails,
+    pub ownership: Ownership,
+}
+
+impl UserToken {
+    /// Floor ask price of the collection of the token
+    pub fn collection_floor_price(&self) -> Option<&Price> {
+        self.token.collection.as_ref()?.floor_ask_price.as_ref()
+    }
+
+    /// Price of the top bid, if the token has a bid and the top bid was requested
+    pub fn top_bid_price(&self) -> Option<&Price> {
+        self.token.top_bid.as_ref()?.price.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserTokensResponse {
+    pub tokens: Vec<UserToken>,
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderSide {
+    Ask,
+    Bid,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SalesSortBy {
+    Price,
+    Time,
+    UpdatedAt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesRequest {
+    // Filter to particular contracts. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub contract: Option<Vec<String>>,
+    // Filter to particular tokens. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub tokens: Option<Vec<String>>,
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular attribute, passed as attributes[key]=value. Requires a collection.
+    #[serde(skip)]
+    pub attributes: Vec<(String, String)>,
+    // Filter to a particular transaction. Example: 0x04654cc4c81882ed4d20b958e0eeb107915d75730110cce65333221439de6afc
+    pub tx_hash: Option<String>,
+    // Get events after a particular unix timestamp (inclusive)
+    pub start_timestamp: Option<u64>,
+    // Get events before a particular unix timestamp (inclusive)
+    pub end_timestamp: Option<u64>,
+    // Filter to sales filling an ask or a bid
+    pub order_side: Option<OrderSide>,
+    // If true, token metadata is included in the response.
+    pub include_token_metadata: Option<bool>,
+    // If true, deleted sales, e.g. from reorgs, are included in the response.
+    pub include_deleted: Option<bool>,
+    // Order the items are returned in the response. Defaults to time.
+    pub sort_by: Option<SalesSortBy>,
+    pub sort_direction: Option<String>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 1000.
+    pub limit: Option<u16>,
+}
+
+impl SalesRequest {
+    /// Query string of the request including the attribute filters
+    pub fn to_query_string(&self) -> Result<String, MagicedenApiError> {
+        query_with_attributes(self, "attributes", &self.attributes)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleFee {
+    // Can be marketplace or royalty
+    pub kind: Option<String>,
+    pub bps: Option<u64>,
+    pub recipient: Option<Address>,
+    pub source: Option<String>,
+    // Fee amount in the smallest unit of the sale currency
+    pub raw_amount: Option<U256>,
+}
+
+/// A sale, i.e. a filled ask or bid
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sale {
+    pub id: Option<String>,
+    pub sale_id: Option<String>,
+    pub token: Option<TokenDetails>,
+    pub order_id: Option<String>,
+    pub order_source: Option<String>,
+    pub order_side: Option<OrderSide>,
+    pub order_kind: Option<Kind>,
+    // Seller of the token
+    pub from: Address,
+    // Buyer of the token
+    pub to: Address,
+    // Quantity sold
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub amount: Option<u64>,
+    pub fill_source: Option<String>,
+    pub block: Option<u64>,
+    pub tx_hash: Option<B256>,
+    pub log_index: Option<u64>,
+    pub batch_index: Option<u64>,
+    // Unix timestamp (seconds) of the block
+    pub timestamp: Option<u64>,
+    pub price: Option<Price>,
+    // Likelihood of wash trading from 0 to 1
+    pub wash_trading_score: Option<f64>,
+    pub royalty_fee_bps: Option<u64>,
+    pub marketplace_fee_bps: Option<u64>,
+    pub paid_full_royalty: Option<bool>,
+    pub royalty_fee_breakdown: Option<Vec<SaleFee>>,
+    pub marketplace_fee_breakdown: Option<Vec<SaleFee>>,
+    pub is_deleted: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Sale {
+    /// Maker of the filled order: the seller if an ask was filled, the buyer if a bid was filled
+    pub fn maker(&self) -> Option<&Address> {
+        match self.order_side.as_ref()? {
+            OrderSide::Ask => Some(&self.from),
+            OrderSide::Bid => Some(&self.to),
+        }
+    }
+
+    /// Taker of the filled order
+    pub fn taker(&self) -> Option<&Address> {
+        match self.order_side.as_ref()? {
+            OrderSide::Ask => Some(&self.to),
+            OrderSide::Bid => Some(&self.from),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SalesResponse {
+    pub sales: Vec<Sale>,
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityType {
+    Sale,
+    Ask,
+    Bid,
+    AskCancel,
+    BidCancel,
+    Transfer,
+    Mint,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Order {
-    pub id: String,
-    pub kind: Kind,
-    pub side: Side,
-    pub status: OrderStatus,
-    pub token_set_id: String,
-    pub token_set_schema_hash: String,
-    pub contract: Option<String>,
-    pub contract_kind: Option<String>,
-    pub maker: String,
-    pub taker: String,
+pub enum ActivitySortBy {
+    EventTimestamp,
+    CreatedAt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionActivityRequest {
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular collection set.
+    pub collections_set_id: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to particular activity types. All types if not set.
+    pub types: Option<Vec<ActivityType>>,
+    // If true, token and collection metadata is included in the response.
+    pub include_metadata: Option<bool>,
+    // If true, activity of spam tokens is excluded.
+    pub exclude_spam: Option<bool>,
+    // Order the items are returned in the response. Defaults to eventTimestamp.
+    pub sort_by: Option<ActivitySortBy>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 1000.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserActivityRequest {
+    // Users to get the activity of. Example: 0xF296178d553C8Ec21A2fBD2c5dDa8CA9ac905A00
+    pub users: Vec<String>,
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular community. Example: artblocks
+    pub community: Option<String>,
+    // Filter to particular activity types. All types if not set.
+    pub types: Option<Vec<ActivityType>>,
+    // If true, token and collection metadata is included in the response.
+    pub include_metadata: Option<bool>,
+    // If true, activity of spam tokens is excluded.
+    pub exclude_spam: Option<bool>,
+    // Order the items are returned in the response. Defaults to eventTimestamp.
+    pub sort_by: Option<ActivitySortBy>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 1000.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityToken {
+    pub token_id: Option<String>,
+    pub token_name: Option<String>,
+    pub token_image: Option<String>,
+    pub is_spam: Option<bool>,
+    pub rarity_score: Option<f64>,
+    pub rarity_rank: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCollection {
+    pub collection_id: Option<String>,
+    pub collection_name: Option<String>,
+    pub collection_image: Option<String>,
+    pub is_spam: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityOrder {
+    pub id: Option<String>,
+    pub side: Option<OrderSide>,
+    pub source: Option<OrderSource>,
+    pub criteria: Option<Value>,
+}
+
+/// A filled order
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleActivity {
+    // Seller of the token
+    pub from_address: Address,
+    // Buyer of the token
+    pub to_address: Address,
     pub price: Option<Price>,
-    pub valid_from: u64,
-    pub valid_until: u64,
-    pub quantity_filled: Option<u64>,
-    pub quantity_remaining: Option<u64>,
-    //pub dynamic_pricing: Option<DynamicPricing>
-    pub critera: Option<Criteria>,
-    pub source: Option<HashMap<String, String>>,
-    pub fee_bps: Option<u64>,
-    pub fee_breakdown: Vec<FeeBreakdown>,
-    pub expiration: u64,
-    pub is_reservoir: Option<bool>,
-    pub is_dynamic: Option<bool>,
-    // Time when added to indexer
-    pub created_at: DateTime<Utc>,
-    // Time when updated in indexer
-    pub updated_at: DateTime<Utc>,
-    // Time when created by maker
-    pub originated_at: Option<DateTime<Utc>>,
-    pub raw_data: Option<HashMap<String, String>>,
-    pub is_native_off_chain_cancellable: Option<bool>,
-    pub depth: Option<Vec<Depth>>,
+    pub amount: Option<u64>,
+    // Unix timestamp (seconds) of the event
+    pub timestamp: u64,
+    pub contract: Option<Address>,
+    pub token: Option<ActivityToken>,
+    pub collection: Option<ActivityCollection>,
+    pub tx_hash: Option<B256>,
+    pub log_index: Option<u64>,
+    pub batch_index: Option<u64>,
+    pub fill_source: Option<OrderSource>,
+    pub order: Option<ActivityOrder>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
+/// A new or cancelled ask or bid
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct AsksResponse {
-    pub orders: Vec<Order>,
+#[serde(rename_all = "camelCase")]
+pub struct OrderActivity {
+    // Maker of the order
+    pub from_address: Address,
+    pub price: Option<Price>,
+    pub amount: Option<u64>,
+    // Unix timestamp (seconds) of the event
+    pub timestamp: u64,
+    pub contract: Option<Address>,
+    pub token: Option<ActivityToken>,
+    pub collection: Option<ActivityCollection>,
+    // Transaction of an on-chain cancellation
+    pub tx_hash: Option<B256>,
+    pub order: Option<ActivityOrder>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// A transfer or mint. Mints are transfers from the zero address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferActivity {
+    pub from_address: Address,
+    pub to_address: Address,
+    // Mint price, if any
+    pub price: Option<Price>,
+    pub amount: Option<u64>,
+    // Unix timestamp (seconds) of the event
+    pub timestamp: u64,
+    pub contract: Option<Address>,
+    pub token: Option<ActivityToken>,
+    pub collection: Option<ActivityCollection>,
+    pub tx_hash: Option<B256>,
+    pub log_index: Option<u64>,
+    pub batch_index: Option<u64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Activity of a collection or user, tagged by its `type`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Activity {
+    Sale(Box<SaleActivity>),
+    Ask(Box<OrderActivity>),
+    Bid(Box<OrderActivity>),
+    AskCancel(Box<OrderActivity>),
+    BidCancel(Box<OrderActivity>),
+    Transfer(Box<TransferActivity>),
+    Mint(Box<TransferActivity>),
+    // Any activity type not known to this client
+    #[serde(untagged)]
+    Other(Value),
+}
+
+// Internally tagged enums do not work with the arbitrary_precision feature of serde_json, so we dispatch on the type
+impl<'de> Deserialize<'de> for Activity {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn from_value<T: serde::de::DeserializeOwned, E: serde::de::Error>(value: Value) -> Result<Box<T>, E> {
+            serde_json::from_value(value).map(Box::new).map_err(E::custom)
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        match value.get("type").and_then(Value::as_str) {
+            Some("sale") => from_value(value).map(Activity::Sale),
+            Some("ask") => from_value(value).map(Activity::Ask),
+            Some("bid") => from_value(value).map(Activity::Bid),
+            Some("ask_cancel") => from_value(value).map(Activity::AskCancel),
+            Some("bid_cancel") => from_value(value).map(Activity::BidCancel),
+            Some("transfer") => from_value(value).map(Activity::Transfer),
+            Some("mint") => from_value(value).map(Activity::Mint),
+            _ => Ok(Activity::Other(value)),
+        }
+    }
+}
+
+impl Activity {
+    /// Type of the activity, `None` for unknown types
+    pub fn activity_type(&self) -> Option<ActivityType> {
+        match self {
+            Activity::Sale(_) => Some(ActivityType::Sale),
+            Activity::Ask(_) => Some(ActivityType::Ask),
+            Activity::Bid(_) => Some(ActivityType::Bid),
+            Activity::AskCancel(_) => Some(ActivityType::AskCancel),
+            Activity::BidCancel(_) => Some(ActivityType::BidCancel),
+            Activity::Transfer(_) => Some(ActivityType::Transfer),
+            Activity::Mint(_) => Some(ActivityType::Mint),
+            Activity::Other(_) => None,
+        }
+    }
+
+    /// Unix timestamp (seconds) of the event
+    pub fn timestamp(&self) -> Option<u64> {
+        match self {
+            Activity::Sale(a) => Some(a.timestamp),
+            Activity::Ask(a) | Activity::Bid(a) | Activity::AskCancel(a) | Activity::BidCancel(a) => Some(a.timestamp),
+            Activity::Transfer(a) | Activity::Mint(a) => Some(a.timestamp),
+            Activity::Other(v) => v.get("timestamp").and_then(Value::as_u64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActivityResponse {
+    pub activities: Vec<Activity>,
     pub continuation: Option<String>,
 }
 
-#[derive(Error, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct MagicedenBuyTokensErrorResponse {
-    pub status_code: u32,
-    pub error: String,
-    pub message: String,
+pub struct OwnersRequest {
+    // Filter to a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Filter to a particular contract. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub contract: Option<String>,
+    // Filter to a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: Option<String>,
+    // Filter to a particular collection set.
+    pub collections_set_id: Option<String>,
+    // Filter to a particular attribute, passed as attributes[key]=value. Requires a collection.
+    #[serde(skip)]
+    pub attributes: Vec<(String, String)>,
+    // Use offset to request the next batch of items. Max is 10000.
+    pub offset: Option<u32>,
+    // Amount of items returned in response. Max limit is 500.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
 }
 
-impl fmt::Display for MagicedenBuyTokensErrorResponse {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Status code: {} Error: {} Message: {}", self.status_code, self.error, self.message)
+impl OwnersRequest {
+    /// Query string of the request including the attribute filters
+    pub fn to_query_string(&self) -> Result<String, MagicedenApiError> {
+        query_with_attributes(self, "attributes", &self.attributes)
     }
 }
 
-#[derive(Error, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct ServerError {
-    pub status_code: u16,
-    pub body: String,
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerOwnership {
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub token_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub on_sale_count: Option<u64>,
+    // Lowest listing of the owner
+    pub floor_ask_price: Option<Price>,
+    // Highest bid on the tokens of the owner
+    pub top_bid_value: Option<Price>,
+    // Sum of the top bids on all tokens of the owner
+    pub total_bid_value: Option<Price>,
 }
-impl fmt::Display for ServerError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Status code {}, Body: {}", self.status_code, self.body)
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Owner {
+    pub address: Address,
+    pub ownership: OwnerOwnership,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OwnersResponse {
+    // Owners sorted by token count, largest holder first
+    pub owners: Vec<Owner>,
+}
+
+/// Holder concentration of a collection
+#[derive(Clone, Debug, PartialEq)]
+pub struct HolderConcentration {
+    // Number of top holders considered
+    pub top_n: usize,
+    // Tokens held by the top holders
+    pub top_n_tokens: u64,
+    // Share of the supply held by the top holders, from 0 to 1
+    pub top_n_share: f64,
+    // Owners divided by the tokens held, from 0 to 1. For ERC721 1 means every token has a different owner. For
+    // ERC1155 the token count is the sum of all balances, so a balance of 5 counts as 5 tokens and 1 means every
+    // owner holds a single unit. It does not tell how many token ids an owner holds.
+    pub unique_owner_ratio: f64,
+}
+
+impl HolderConcentration {
+    /// Compute the concentration of a collection with `token_count` tokens held by `owner_count` owners. `owners`
+    /// must contain at least the `top_n` largest holders.
+    pub fn new(owners: &[Owner], top_n: usize, token_count: u64, owner_count: u64) -> Self {
+        let mut counts: Vec<u64> = owners.iter().map(|owner| owner.ownership.token_count.unwrap_or(0)).collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let top_n_tokens = counts.iter().take(top_n).sum();
+
+        let ratio = |n: u64| if token_count == 0 { 0.0 } else { n as f64 / token_count as f64 };
+        Self { top_n, top_n_tokens, top_n_share: ratio(top_n_tokens), unique_owner_ratio: ratio(owner_count) }
     }
 }
 
-#[derive(Error, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValue {
+    pub value: String,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub count: Option<u64>,
+    pub floor_ask_price: Option<Price>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeKey {
+    pub key: String,
+    // Number of distinct values of the key
+    pub attribute_count: Option<u64>,
+    // string, number, date or range
+    pub kind: Option<String>,
+    pub min_range: Option<f64>,
+    pub max_range: Option<f64>,
+    pub values: Vec<AttributeValue>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttributesAllResponse {
+    pub attributes: Vec<AttributeKey>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExploreAttributesRequest {
+    // Filter to the attributes of a particular token. Example: 1
+    pub token_id: Option<String>,
+    // Filter to a particular attribute key. Example: Background
+    pub attribute_key: Option<String>,
+    // If true, the top bid is included in the response.
+    pub include_top_bid: Option<bool>,
+    // If true, range traits are excluded.
+    pub exclude_range_traits: Option<bool>,
+    // If true, number traits are excluded.
+    pub exclude_number_traits: Option<bool>,
+    // Max number of floor asks returned per attribute. Max is 20.
+    pub max_floor_ask_prices: Option<u8>,
+    // Max number of last sales returned per attribute. Max is 20.
+    pub max_last_sells: Option<u8>,
+    // Use continuation token to request next offset of items.
+    pub continuation: Option<String>,
+    // Amount of items returned in response. Max limit is 5000.
+    pub limit: Option<u16>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeSale {
+    pub token_id: Option<String>,
+    // Price in the native currency
+    pub value: Option<f64>,
+    // Unix timestamp (seconds) of the sale
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeTopBid {
+    pub id: Option<String>,
+    pub value: Option<Price>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExploredAttribute {
+    pub key: String,
+    pub value: String,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub token_count: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_count")]
+    pub on_sale_count: Option<u64>,
+    // Lowest asks of the attribute, lowest first
+    pub floor_ask_prices: Option<Vec<Price>>,
+    pub top_bid: Option<AttributeTopBid>,
+    pub last_buys: Option<Vec<AttributeSale>>,
+    pub last_sells: Option<Vec<AttributeSale>>,
+    pub sample_images: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExploreAttributesResponse {
+    pub attributes: Vec<ExploredAttribute>,
+    pub continuation: Option<String>,
+}
+
+/// Stats of one trait, i.e. attribute key and value, of a collection
+#[derive(Clone, Debug)]
+pub struct TraitStats {
+    pub key: String,
+    pub value: String,
+    // string, number, date or range
+    pub kind: Option<String>,
+    pub token_count: Option<u64>,
+    pub on_sale_count: Option<u64>,
+    pub floor_ask_price: Option<Price>,
+    pub top_bid: Option<Price>,
+    pub last_sells: Vec<AttributeSale>,
+}
+
+/// Stats of all traits of a collection
+#[derive(Clone, Debug, Default)]
+pub struct CollectionAttributes {
+    pub traits: Vec<TraitStats>,
+}
+
+impl CollectionAttributes {
+    /// Merge the attribute values of the all endpoint with the stats of the explore endpoint.
+    pub fn new(all: Vec<AttributeKey>, explored: Vec<ExploredAttribute>) -> Self {
+        let index: HashMap<(String, String), usize> =
+            explored.iter().enumerate().map(|(i, attr)| ((attr.key.clone(), attr.value.clone()), i)).collect();
+        let mut explored: Vec<Option<ExploredAttribute>> = explored.into_iter().map(Some).collect();
+
+        let mut traits = Vec::new();
+        for key in all {
+            for value in key.values {
+                let stats = index.get(&(key.key.clone(), value.value.clone())).and_then(|&i| explored[i].take());
+                traits.push(TraitStats {
+                    key: key.key.clone(),
+                    value: value.value,
+                    kind: key.kind.clone(),
+                    token_count: value.count.or(stats.as_ref().and_then(|s| s.token_count)),
+                    floor_ask_price: value.floor_ask_price.or_else(|| stats.as_ref().and_then(ExploredAttribute::floor_ask_price).cloned()),
+                    on_sale_count: stats.as_ref().and_then(|s| s.on_sale_count),
+                    top_bid: stats.as_ref().and_then(|s| s.top_bid.as_ref()?.value.clone()),
+                    last_sells: stats.and_then(|s| s.last_sells).unwrap_or_default(),
+                });
+            }
+        }
+
+        // Traits only known to the explore endpoint, e.g. if the all endpoint is stale
+        traits.extend(explored.into_iter().flatten().map(|stats| TraitStats {
+            floor_ask_price: stats.floor_ask_price().cloned(),
+            top_bid: stats.top_bid.and_then(|bid| bid.value),
+            key: stats.key,
+            value: stats.value,
+            kind: None,
+            token_count: stats.token_count,
+            on_sale_count: stats.on_sale_count,
+            last_sells: stats.last_sells.unwrap_or_default(),
+        }));
+        Self { traits }
+    }
+
+    /// Stats of the trait with the given key and value
+    pub fn get(&self, key: &str, value: &str) -> Option<&TraitStats> {
+        self.traits.iter().find(|t| t.key == key && t.value == value)
+    }
+}
+
+impl ExploredAttribute {
+    /// Lowest ask of the attribute
+    pub fn floor_ask_price(&self) -> Option<&Price> {
+        self.floor_ask_prices.as_ref()?.first()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDepthRequest {
+    // buy for the bid depth, sell for the ask depth
+    pub side: Side,
+    // Depth of a particular token. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63:123
+    pub token: Option<String>,
+    // Depth of a particular collection with collection-id. Example: 0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63
+    pub collection: Option<String>,
+    // Return result in given currency
+    pub display_currency: Option<String>,
+}
+
+impl OrderDepthRequest {
+    pub fn token(side: Side, token: impl Into<String>) -> Self {
+        Self { side, token: Some(token.into()), collection: None, display_currency: None }
+    }
+
+    pub fn collection(side: Side, collection: impl Into<String>) -> Self {
+        Self { side, token: None, collection: Some(collection.into()), display_currency: None }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrderDepthResponse {
+    // Price levels, not guaranteed to be ordered by price
+    pub depth: Vec<Depth>,
+}
+
+impl OrderDepthResponse {
+    /// Total quantity over all price levels
+    pub fn total_quantity(&self) -> Decimal {
+        self.depth.iter().map(|level| level.quantity).sum()
+    }
+
+    /// Simulate filling `quantity` against the price levels of `side`, best price first: the lowest asks for
+    /// [`Side::Sell`] and the highest bids for [`Side::Buy`]. Returns the total price, or `None` if `quantity` is
+    /// negative, the depth is not sufficient or the total price overflows.
+    pub fn fill_cost(&self, side: &Side, quantity: Decimal) -> Option<Decimal> {
+        if quantity.is_sign_negative() {
+            return None;
+        }
+        let mut levels: Vec<&Depth> = self.depth.iter().collect();
+        match side {
+            Side::Sell => levels.sort_by_key(|level| level.price),
+            Side::Buy => levels.sort_by_key(|level| std::cmp::Reverse(level.price)),
+        }
+
+        let mut remaining = quantity;
+        let mut cost = Decimal::ZERO;
+        for level in levels {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let filled = remaining.min(level.quantity);
+            cost = cost.checked_add(filled.checked_mul(level.price)?)?;
+            remaining -= filled;
+        }
+        (remaining <= Decimal::ZERO).then_some(cost)
+    }
+}
+
+/// Error body returned by the API, either as `{ msg, errors }` or as `{ statusCode, error, message, code }`.
+#[derive(Error, Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
 pub struct MagicedenErrorResponse {
+    #[serde(default, alias = "message")]
     pub msg: String,
+    #[serde(default)]
     pub errors: Vec<String>,
+    // Short description of the status, e.g. "Bad Request"
+    #[serde(default)]
+    pub error: Option<String>,
+    // Magic Eden specific error code
+    #[serde(default)]
+    pub code: Option<u32>,
 }
 
 impl fmt::Display for MagicedenErrorResponse {
@@ -624,37 +2640,186 @@ impl fmt::Display for MagicedenErrorResponse {
     }
 }
 
+/// Non-success response of the API.
 #[derive(Error, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct MagicedenErrorParseResponse {
-    pub body: String,
+pub struct ApiErrorResponse {
     pub status_code: u16,
-    pub error: String,
+    pub body: String,
+    // Decoded error body, if the API returned one
+    pub error: Option<MagicedenErrorResponse>,
+    // Wait time requested by the API with the Retry-After header
+    pub retry_after: Option<Duration>,
 }
 
-impl fmt::Display for MagicedenErrorParseResponse {
+impl ApiErrorResponse {
+    pub(crate) fn new(status_code: u16, body: String, retry_after: Option<Duration>) -> Self {
+        let error = serde_json::from_str::<MagicedenErrorResponse>(&body)
+            .ok()
+            .filter(|e| !e.msg.is_empty() || !e.errors.is_empty() || e.error.is_some());
+        Self { status_code, body, error, retry_after }
+    }
+
+    /// Error message of the API, if the body could be decoded.
+    pub fn message(&self) -> Option<&str> {
+        self.error.as_ref().map(|e| e.msg.as_str()).filter(|m| !m.is_empty())
+    }
+}
+
+impl fmt::Display for ApiErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Body: {} Error: {}", self.body, self.error)
+        match self.message() {
+            Some(message) => write!(f, "Status code {}, Message: {}", self.status_code, message),
+            None => write!(f, "Status code {}, Body: {}", self.status_code, self.body),
+        }
     }
 }
 
 #[derive(Error, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MagicedenOrderAlreadyFilledError {
-    pub status_code: u32,
+pub struct MagicedenErrorParseResponse {
+    pub body: String,
+    pub status_code: u16,
     pub error: String,
-    pub message: String,
-    pub code: u32,
 }
 
-impl fmt::Display for MagicedenOrderAlreadyFilledError {
+impl fmt::Display for MagicedenErrorParseResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Already filled: status_code={}, error={}, message={}, code={}", self.status_code, self.error, self.message, self.code)
+        write!(f, "Body: {} Error: {}", self.body, self.error)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
 
     #[test]
     fn test_serialize_buy_tokens_request() {}
+
+    #[test]
+    fn deserializes_buy_token_errors_without_order_hash() {
+        let errors: Vec<BuyTokenError> = serde_json::from_value(serde_json::json!([
+            { "message": "Order is not fillable", "orderId": "0x5844792a36ff5966a325d2180ebda80f8f63a7f3d4585e1c88615a111ce42942" },
+            { "message": "Mint is sold out", "orderId": "mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63" },
+            { "message": "Insufficient balance" },
+        ]))
+        .unwrap();
+        assert_eq!(errors[1].order_id.as_deref(), Some("mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63"));
+        assert!(errors[2].order_id.is_none());
+    }
+
+    #[test]
+    fn deserializes_mint_order_ids() {
+        let item: ExecuteStepItem = serde_json::from_value(serde_json::json!({
+            "status": "incomplete",
+            "orderIds": ["mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63"],
+        }))
+        .unwrap();
+        assert_eq!(item.order_ids.unwrap()[0], "mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63");
+
+        let outcome: OrderOutcome = serde_json::from_value(
+            serde_json::json!({ "orderId": "mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63", "status": "success" }),
+        )
+        .unwrap();
+        assert_eq!(outcome.order_id.as_deref(), Some("mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63"));
+    }
+
+    #[test]
+    fn computes_concentration_of_multi_balance_owners() {
+        // ERC1155 owners with several units each: 3 owners hold all 12 units of the supply
+        let owners: Vec<Owner> = serde_json::from_value(serde_json::json!([
+            { "address": "0x01", "ownership": { "tokenCount": "2" } },
+            { "address": "0x02", "ownership": { "tokenCount": "7" } },
+            { "address": "0x03", "ownership": { "tokenCount": "3" } },
+        ]))
+        .unwrap();
+
+        let concentration = HolderConcentration::new(&owners, 1, 12, 3);
+
+        assert_eq!(concentration.top_n_tokens, 7);
+        assert!((concentration.top_n_share - 7.0 / 12.0).abs() < 1e-9);
+        // Every unit counts as a token, so 3 owners of 12 units give 0.25 even though no unit is shared
+        assert!((concentration.unique_owner_ratio - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn keeps_explored_attributes_missing_in_all() {
+        let all: Vec<AttributeKey> = serde_json::from_value(serde_json::json!([
+            { "key": "Background", "kind": "string", "values": [{ "value": "Blue", "count": 412 }] }
+        ]))
+        .unwrap();
+        let explored: Vec<ExploredAttribute> = serde_json::from_value(serde_json::json!([
+            { "key": "Eyes", "value": "Laser", "tokenCount": 3, "onSaleCount": 1 },
+            { "key": "Background", "value": "Blue", "tokenCount": 412, "onSaleCount": 37 },
+        ]))
+        .unwrap();
+
+        let attributes = CollectionAttributes::new(all, explored);
+
+        assert_eq!(attributes.traits.len(), 2);
+        assert_eq!(attributes.get("Background", "Blue").unwrap().on_sale_count, Some(37));
+        let laser = attributes.get("Eyes", "Laser").unwrap();
+        assert_eq!(laser.token_count, Some(3));
+        assert_eq!(laser.on_sale_count, Some(1));
+        assert!(laser.kind.is_none());
+    }
+
+    #[test]
+    fn classifies_mint_errors() {
+        use MintErrorKind::*;
+        let cases = [
+            ("Mint has not started yet", Some(NotStarted)),
+            ("Mint is closed: not-yet-started", Some(NotStarted)),
+            ("The mint has ended", Some(Ended)),
+            ("Mint is closed: mint ended", Some(Ended)),
+            ("Collection is sold out", Some(SoldOut)),
+            ("Requested quantity exceeds max supply", Some(SoldOut)),
+            ("max-supply-exceeded", Some(SoldOut)),
+            ("Max mints per wallet exceeded", Some(MaxPerWallet)),
+            ("Unable to mint requested quantity (max 2 per wallet)", Some(MaxPerWallet)),
+            ("Collection has no eligible mints", Some(NotEligible)),
+            ("Taker is not on the allowlist", Some(NotEligible)),
+            // Not caused by the mint phase
+            ("Recommended gas price is too low", None),
+            ("Request body was extended with unknown fields", None),
+            ("Comment appended to the mint is invalid", None),
+            ("Currency not allowed", None),
+            ("Invalid max supply parameter", None),
+            ("Balance too low to proceed with transaction", None),
+            ("\"taker\" must be a valid address", None),
+        ];
+        for (message, kind) in cases {
+            assert_eq!(MintErrorKind::from_message(message), kind, "{message}");
+        }
+    }
+
+    #[test]
+    fn converts_sign_data_to_typed_data() {
+        let sign: SignData = serde_json::from_value(serde_json::json!({
+            "signatureKind": "eip712",
+            "domain": { "name": "Seaport", "version": "1.6", "chainId": 1, "verifyingContract": "0x0000000000000068f116a894984e2db1123eb395" },
+            "types": { "OrderHashes": [{ "name": "orderHashes", "type": "bytes32[]" }] },
+            "value": { "orderHashes": [] },
+            "primaryType": "OrderHashes"
+        }))
+        .unwrap();
+
+        let typed = serde_json::to_value(sign.to_typed_data().unwrap()).unwrap();
+        assert_eq!(
+            typed["types"]["EIP712Domain"],
+            serde_json::json!([
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ])
+        );
+        assert_eq!(typed["primaryType"], "OrderHashes");
+        assert_eq!(typed["domain"]["chainId"], 1);
+        assert!(typed["message"]["orderHashes"].is_array());
+    }
+
+    #[test]
+    fn rejects_eip191_as_typed_data() {
+        let sign: SignData = serde_json::from_value(serde_json::json!({ "signatureKind": "eip191", "message": "hello" })).unwrap();
+        assert!(matches!(sign.to_typed_data(), Err(MagicedenApiError::InvalidArgument(_))));
+    }
 }
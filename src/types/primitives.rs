@@ -0,0 +1,131 @@
+//! Primitive types of the API. They hold the value as returned by the API, so their type does not depend on enabled
+//! features. With the `alloy` feature they convert to and from the alloy primitives.
+
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// Exact decimal number, e.g. a fractional price or quantity
+pub use rust_decimal::Decimal;
+
+macro_rules! primitive {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Parse the value as alloy primitive.
+            #[cfg(feature = "alloy")]
+            pub fn to_alloy(&self) -> Result<alloy_primitives::$name, <alloy_primitives::$name as FromStr>::Err> {
+                self.0.parse()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(s.to_string()))
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        #[cfg(feature = "alloy")]
+        impl From<alloy_primitives::$name> for $name {
+            fn from(value: alloy_primitives::$name) -> Self {
+                Self(value.to_string())
+            }
+        }
+
+        #[cfg(feature = "alloy")]
+        impl TryFrom<&$name> for alloy_primitives::$name {
+            type Error = <alloy_primitives::$name as FromStr>::Err;
+
+            fn try_from(value: &$name) -> Result<Self, Self::Error> {
+                value.to_alloy()
+            }
+        }
+    };
+}
+
+primitive!(
+    /// EVM address
+    Address
+);
+
+primitive!(
+    /// 32 byte hash, e.g. a transaction hash
+    B256
+);
+
+primitive!(
+    /// Raw amount in the smallest unit of the currency, as decimal string
+    U256
+);
+
+primitive!(
+    /// Hex encoded bytes, e.g. transaction calldata
+    Bytes
+);
+
+#[cfg(all(test, feature = "alloy"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_and_from_alloy() {
+        let amount: U256 = alloy_primitives::U256::from(2_000_000_000_000_000_000u128).into();
+        assert_eq!(amount, "2000000000000000000");
+        assert_eq!(amount.to_alloy().unwrap(), alloy_primitives::U256::from(2_000_000_000_000_000_000u128));
+
+        let address: Address = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".parse().unwrap();
+        assert_eq!(alloy_primitives::Address::try_from(&address).unwrap(), address.to_alloy().unwrap());
+        assert!(Address::from("0x1234").to_alloy().is_err());
+    }
+}
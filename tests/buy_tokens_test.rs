@@ -1,8 +1,15 @@
 mod common;
 
-use common::test_client;
+use common::{mock_client, test_client};
 
-use magiceden_client_rs::types::api::{BuyTokensRequest, Listing};
+use magiceden_client_rs::types::{
+    api::{BuyTokensRequest, Listing},
+    MagicedenApiError,
+};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
 
 // Test is optional because it requires an active order
 #[ignore]
@@ -15,7 +22,7 @@ async fn can_buy_tokens() {
             order_id: Some("0x260a17195de36319209a099f2f90527b7e40e99724e7f8426e947c8f7b325e8d".to_string()),
             ..Default::default()
         }],
-        taker: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        taker: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap(),
         ..Default::default()
     };
 
@@ -24,3 +31,26 @@ async fn can_buy_tokens() {
     assert_eq!(res.path.len(), 1);
     assert_eq!(res.steps.len(), 2);
 }
+
+#[tokio::test]
+async fn reports_already_filled_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/buy/v7"))
+        .respond_with(
+            ResponseTemplate::new(410).set_body_string(r#"{"statusCode":410,"error":"Gone","message":"Order is not fillable","code":1}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = BuyTokensRequest { items: vec![Listing { order_id: Some("0x01".to_string()), ..Default::default() }], ..Default::default() };
+
+    let err = client.buy_tokens(req).await.unwrap_err();
+
+    assert!(matches!(err, MagicedenApiError::Gone(_)));
+    assert_eq!(err.status_code(), Some(410));
+    assert_eq!(err.api_error().unwrap().message(), Some("Order is not fillable"));
+}
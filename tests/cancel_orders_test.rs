@@ -0,0 +1,52 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{CancelRequest, StepId};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_cancel_orders_off_chain() {
+    let server = MockServer::start().await;
+    let order_id = "0x5844792a36ff5966a325d2180ebda80f8f63a7f3d4585e1c88615a111ce42942";
+    let body = fixture("response_cancel.json");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/cancel/v3"))
+        .and(body_json(json!({ "orderIds": [order_id] })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.cancel_orders(CancelRequest::orders([order_id])).await.unwrap();
+
+    assert!(res.is_off_chain());
+    let step = res.steps.first().unwrap();
+    assert_eq!(step.id, StepId::CancellationSignature);
+    let sig = step.items[0].data.as_ref().unwrap().as_signature().unwrap();
+    assert_eq!(sig.sign.primary_type.as_deref(), Some("OrderHashes"));
+    assert_eq!(sig.post.as_ref().unwrap().endpoint, "/execute/cancel-signature/v1");
+}
+
+#[tokio::test]
+async fn nothing_to_cancel_is_not_off_chain() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/cancel/v3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "steps": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.cancel_orders(CancelRequest::orders(["0x01"])).await.unwrap();
+
+    assert!(res.steps.is_empty());
+    assert!(!res.is_off_chain());
+}
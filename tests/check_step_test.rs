@@ -0,0 +1,121 @@
+mod common;
+use common::mock_client;
+
+use magiceden_client_rs::types::{
+    api::{BuyTokenCheck, BuyTokenCheckBody, BuyTokensKind, ExecuteStatus, Method},
+    MagicedenApiError,
+};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::{
+    matchers::{body_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const TX_HASH: &str = "0x1f8b3c6a0e2d4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f90";
+
+fn transaction_check() -> BuyTokenCheck {
+    BuyTokenCheck {
+        endpoint: "/execute/status/v1".to_string(),
+        method: Method::Post,
+        body: BuyTokenCheckBody { kind: BuyTokensKind::Transaction, id: None, tx_hash: None },
+    }
+    .with_tx_hash(TX_HASH.parse().unwrap())
+}
+
+#[tokio::test]
+async fn waits_until_step_is_final() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .and(body_json(json!({ "kind": "transaction", "txHash": TX_HASH })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "pending" })))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "success",
+            "txHashes": [TX_HASH],
+            "time": 1714000000,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let status = client.wait_for_step(&transaction_check(), Duration::from_secs(5), Duration::from_millis(10)).await.unwrap();
+
+    assert_eq!(status.status, ExecuteStatus::Success);
+    assert_eq!(status.tx_hashes.unwrap().len(), 1);
+    assert_eq!(status.time, Some(1714000000));
+}
+
+#[tokio::test]
+async fn times_out_on_pending_step() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "received" })))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.wait_for_step(&transaction_check(), Duration::from_millis(50), Duration::from_millis(20)).await;
+
+    assert!(matches!(res, Err(MagicedenApiError::Timeout(_))));
+}
+
+#[tokio::test]
+async fn times_out_on_slow_status_check() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "success" })).set_delay(Duration::from_millis(500)))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.wait_for_step(&transaction_check(), Duration::from_millis(100), Duration::from_millis(10)).await;
+
+    assert!(matches!(res, Err(MagicedenApiError::Timeout(_))));
+}
+
+#[tokio::test]
+async fn sleeps_at_most_until_deadline() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "pending" })))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let start = std::time::Instant::now();
+    let res = client.wait_for_step(&transaction_check(), Duration::from_millis(100), Duration::from_secs(10)).await;
+
+    assert!(matches!(res, Err(MagicedenApiError::Timeout(_))));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn rejects_unsupported_check_method() {
+    let check: BuyTokenCheck = serde_json::from_value(json!({
+        "endpoint": "/execute/status/v1",
+        "method": "GET",
+        "body": { "kind": "transaction" },
+    }))
+    .unwrap();
+    assert!(matches!(check.method, Method::Other));
+
+    let client = mock_client("http://localhost:1".to_string());
+
+    let err = client.check_step_status(&check).await.unwrap_err();
+    assert!(matches!(err, MagicedenApiError::InvalidStep(_)));
+}
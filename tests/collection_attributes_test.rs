@@ -0,0 +1,58 @@
+mod common;
+use common::{fixture, mock_client};
+
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const COLLECTION: &str = "0x8132176f60e9db5288d84feeefae455c8caa3f95";
+
+#[tokio::test]
+async fn can_retrieve_collection_attributes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/rtp/ethereum/collections/{COLLECTION}/attributes/all/v4")))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_attributes_all.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/rtp/ethereum/collections/{COLLECTION}/attributes/explore/v5")))
+        .and(query_param("includeTopBid", "true"))
+        .and(query_param_is_missing("continuation"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_attributes_explore.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/rtp/ethereum/collections/{COLLECTION}/attributes/explore/v5")))
+        .and(query_param("continuation", "cGFnZTI="))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_attributes_explore_last_page.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let attributes = client.retrieve_collection_attributes(COLLECTION).await.unwrap();
+
+    assert_eq!(attributes.traits.len(), 4);
+
+    let blue = attributes.get("Background", "Blue").unwrap();
+    assert_eq!(blue.token_count, Some(412));
+    assert_eq!(blue.on_sale_count, Some(37));
+    assert_eq!(blue.floor_ask_price.as_ref().unwrap().amount.raw.to_string(), "48000000000000000");
+    assert_eq!(blue.top_bid.as_ref().unwrap().amount.raw.to_string(), "42000000000000000");
+    assert_eq!(blue.last_sells[0].token_id.as_deref(), Some("653"));
+
+    // Explored on the second page
+    let red = attributes.get("Background", "Red").unwrap();
+    assert_eq!(red.floor_ask_price.as_ref().unwrap().amount.raw.to_string(), "46000000000000000");
+
+    // Not explored, only the count is known
+    let level = attributes.get("Level", "7").unwrap();
+    assert_eq!(level.kind.as_deref(), Some("number"));
+    assert_eq!(level.token_count, Some(950));
+    assert!(level.floor_ask_price.is_none());
+}
@@ -1,5 +1,6 @@
 use magiceden_client_rs::{types::Chain, MagicedenApiConfig, MagicedenClient};
 
+#[allow(dead_code)]
 pub fn test_client() -> MagicedenClient {
     let cfg = MagicedenApiConfig { chain: Chain::Ethereum, ..Default::default() };
 
@@ -12,3 +13,16 @@ pub fn live_client() -> MagicedenClient {
 
     MagicedenClient::new(cfg)
 }
+
+#[allow(dead_code)]
+pub fn mock_client(base_url: String) -> MagicedenClient {
+    let cfg = MagicedenApiConfig { base_url: Some(base_url), ..Default::default() };
+
+    MagicedenClient::new(cfg)
+}
+
+/// Read a response fixture from the resources directory.
+#[allow(dead_code)]
+pub fn fixture(name: &str) -> String {
+    std::fs::read_to_string(format!("{}/resources/{name}", env!("CARGO_MANIFEST_DIR"))).unwrap()
+}
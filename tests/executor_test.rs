@@ -0,0 +1,203 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::{
+    types::{
+        api::{
+            BuyTokenItemData, BuyTokensResponse, ExecuteStatus, ListTokensResponse, MintTokensResponse, SellTokensResponse, SignData,
+            StepId,
+        },
+        primitives::{Bytes, B256},
+        MagicedenApiError,
+    },
+    ExecuteOptions, StepExecutor, StepSigner, TransactionSender,
+};
+use serde_json::json;
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use wiremock::{
+    matchers::{body_partial_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const SIGNATURE: &str = "0x1234";
+const TX_HASH: &str = "0x1f8b3c6a0e2d4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f90";
+
+struct FakeSigner;
+
+impl StepSigner for FakeSigner {
+    type Error = Infallible;
+
+    async fn sign_typed_data(&self, data: &SignData) -> Result<Bytes, Self::Error> {
+        assert!(data.primary_type.is_some());
+        Ok(SIGNATURE.parse().unwrap())
+    }
+
+    async fn sign_message(&self, _message: &str) -> Result<Bytes, Self::Error> {
+        Ok(SIGNATURE.parse().unwrap())
+    }
+}
+
+struct FakeSender;
+
+impl TransactionSender for FakeSender {
+    type Error = Infallible;
+
+    async fn send_transaction(&self, tx: &BuyTokenItemData) -> Result<B256, Self::Error> {
+        assert!(!tx.data.as_str().is_empty());
+        Ok(TX_HASH.parse().unwrap())
+    }
+}
+
+/// Records how many requests the mock server had received when each transaction was sent.
+struct RecordingSender {
+    server: Arc<MockServer>,
+    sent: Arc<Mutex<Vec<usize>>>,
+}
+
+impl TransactionSender for RecordingSender {
+    type Error = Infallible;
+
+    async fn send_transaction(&self, _tx: &BuyTokenItemData) -> Result<B256, Self::Error> {
+        let received = self.server.received_requests().await.unwrap_or_default().len();
+        self.sent.lock().unwrap().push(received);
+        Ok(TX_HASH.parse().unwrap())
+    }
+}
+
+fn options() -> ExecuteOptions {
+    ExecuteOptions { check_timeout: Duration::from_secs(5), check_interval: Duration::from_millis(10) }
+}
+
+#[tokio::test]
+async fn executes_listing_steps_and_posts_signature() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/order/v4"))
+        .and(query_param("signature", SIGNATURE))
+        .and(body_partial_json(json!({ "source": "magiceden.io" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "message": "Success" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let res: ListTokensResponse = serde_json::from_str(&fixture("response_list.json")).unwrap();
+    let executor = StepExecutor::new(mock_client(server.uri()), FakeSigner, FakeSender).with_options(options());
+
+    let executed = executor.execute(res.steps).await.unwrap();
+
+    assert_eq!(executed.len(), 2);
+    assert_eq!(executed[0].step_id, StepId::NftApproval);
+    assert!(executed[0].tx_hash.is_some());
+    assert_eq!(executed[1].step_id, StepId::OrderSignature);
+    assert!(executed[1].signature.is_some());
+}
+
+#[tokio::test]
+async fn executes_buy_steps_and_polls_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .and(body_partial_json(json!({ "kind": "transaction", "txHash": TX_HASH })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "success", "txHashes": [TX_HASH] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let res: BuyTokensResponse = serde_json::from_str(&fixture("response_buy_magiceden.json")).unwrap();
+    let executor = StepExecutor::new(mock_client(server.uri()), FakeSigner, FakeSender).with_options(options());
+
+    let executed = executor.execute(res.steps).await.unwrap();
+
+    assert_eq!(executed.len(), 1);
+    assert_eq!(executed[0].step_id, StepId::Sale);
+    assert_eq!(executed[0].status.as_ref().unwrap().status, ExecuteStatus::Success);
+}
+
+#[tokio::test]
+async fn executes_mint_steps_and_polls_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .and(body_partial_json(json!({ "kind": "transaction", "txHash": TX_HASH })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "success", "txHashes": [TX_HASH] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let res: MintTokensResponse = serde_json::from_str(&fixture("response_mint.json")).unwrap();
+    let executor = StepExecutor::new(mock_client(server.uri()), FakeSigner, FakeSender).with_options(options());
+
+    let executed = executor.execute(res.steps).await.unwrap();
+
+    assert_eq!(executed.len(), 1);
+    assert_eq!(executed[0].step_id, StepId::Sale);
+    assert_eq!(executed[0].tx_hash, Some(TX_HASH.parse().unwrap()));
+}
+
+#[tokio::test]
+async fn fails_on_failed_step() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "failure", "details": "reverted" })))
+        .mount(&server)
+        .await;
+
+    let res: BuyTokensResponse = serde_json::from_str(&fixture("response_buy_magiceden.json")).unwrap();
+    let executor = StepExecutor::new(mock_client(server.uri()), FakeSigner, FakeSender).with_options(options());
+
+    let err = executor.execute(res.steps).await.unwrap_err();
+
+    assert!(matches!(err, MagicedenApiError::StepFailed(status) if status.details.as_deref() == Some("reverted")));
+}
+
+#[tokio::test]
+async fn waits_for_each_item_before_the_next_one() {
+    let server = Arc::new(MockServer::start().await);
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "pending" })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/status/v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "success" })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    // Approval and sale are both returned upfront and executed without requesting the steps again
+    let mut res: serde_json::Value = serde_json::from_str(&fixture("response_sell.json")).unwrap();
+    res["steps"][1]["items"][0]["status"] = json!("incomplete");
+    let res: SellTokensResponse = serde_json::from_value(res).unwrap();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let sender = RecordingSender { server: server.clone(), sent: sent.clone() };
+    let executor = StepExecutor::new(mock_client(server.uri()), FakeSigner, sender).with_options(options());
+
+    let executed = executor.execute(res.steps).await.unwrap();
+
+    assert_eq!(executed.len(), 2);
+    assert_eq!(executed[0].step_id, StepId::NftApproval);
+    assert_eq!(executed[1].step_id, StepId::Sale);
+    assert!(executed.iter().all(|item| item.tx_hash.is_some()));
+    // The sale is sent after the approval was polled until success
+    assert_eq!(*sent.lock().unwrap(), vec![0, 2]);
+}
+
+#[tokio::test]
+async fn rejects_incomplete_item_without_data() {
+    let mut res: serde_json::Value = serde_json::from_str(&fixture("response_buy_magiceden.json")).unwrap();
+    res["steps"][1]["items"][0].as_object_mut().unwrap().remove("data");
+    let res: BuyTokensResponse = serde_json::from_value(res).unwrap();
+    let executor = StepExecutor::new(mock_client("http://localhost:1".to_string()), FakeSigner, FakeSender).with_options(options());
+
+    let err = executor.execute(res.steps).await.unwrap_err();
+
+    assert!(matches!(err, MagicedenApiError::InvalidStep(_)));
+}
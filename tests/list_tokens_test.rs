@@ -0,0 +1,57 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{BuyTokensKind, ListTokensRequest, ListingParams, Orderbook, RawOrderKind, SignatureKind};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_list_tokens() {
+    let server = MockServer::start().await;
+    let body = fixture("response_list.json");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/list/v5"))
+        .and(body_partial_json(json!({
+            "maker": "0xd8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "params": [{
+                "token": "0x8132176f60e9db5288d84feeefae455c8caa3f95:653",
+                "weiPrice": "50000000000000000",
+                "orderKind": "seaport-v1.6",
+                "orderbook": "reservoir",
+            }],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = ListTokensRequest {
+        maker: "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".parse().unwrap(),
+        params: vec![ListingParams {
+            token: "0x8132176f60e9db5288d84feeefae455c8caa3f95:653".to_string(),
+            wei_price: "50000000000000000".parse().unwrap(),
+            order_kind: Some(RawOrderKind::SeaportV16),
+            orderbook: Some(Orderbook::Reservoir),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let res = client.list_tokens(req).await.unwrap();
+
+    assert_eq!(res.steps.len(), 2);
+    assert!(res.steps[0].items[0].data.as_ref().unwrap().as_transaction().is_some());
+
+    let step = &res.steps[1];
+    assert_eq!(step.kind, BuyTokensKind::Signature);
+    let sig = step.items[0].data.as_ref().unwrap().as_signature().unwrap();
+    assert_eq!(sig.sign.signature_kind, SignatureKind::Eip712);
+    assert_eq!(sig.sign.domain.as_ref().unwrap().chain_id, Some(1));
+    assert_eq!(sig.sign.primary_type.as_deref(), Some("OrderComponents"));
+    assert_eq!(sig.post.as_ref().unwrap().endpoint, "/order/v4");
+}
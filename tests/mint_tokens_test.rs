@@ -0,0 +1,141 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::{
+    api::{MintErrorKind, MintItem, MintRequest, StepId},
+    primitives::Decimal,
+    MagicedenApiError,
+};
+use serde_json::json;
+use std::str::FromStr;
+use wiremock::{
+    matchers::{body_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const COLLECTION: &str = "0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63";
+const TAKER: &str = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045";
+
+fn mint_request() -> MintRequest {
+    MintRequest {
+        items: vec![MintItem { collection: Some(COLLECTION.to_string()), quantity: Some(2), ..Default::default() }],
+        taker: TAKER.parse().unwrap(),
+        referrer: Some("0x0000000000000000000000000000000000000001".parse().unwrap()),
+        comment: Some("gm".to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn can_mint_tokens() {
+    let server = MockServer::start().await;
+    let body = fixture("response_mint.json");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/mint/v1"))
+        .and(body_json(json!({
+            "items": [{ "collection": COLLECTION, "quantity": 2 }],
+            "taker": TAKER,
+            "referrer": "0x0000000000000000000000000000000000000001",
+            "comment": "gm"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.mint_tokens(mint_request()).await.unwrap();
+
+    assert_eq!(res.steps[0].id, StepId::Sale);
+    assert!(res.steps[0].items[0].data.as_ref().unwrap().as_transaction().is_some());
+    let max = res.max_quantities().next().unwrap();
+    assert_eq!(max.item_index, 0);
+    assert_eq!(max.max_quantity, "2");
+    assert_eq!(res.path[0].quantity, Some(2));
+    assert_eq!(res.path[0].quote, Some(Decimal::from_str("0.001554").unwrap()));
+}
+
+#[tokio::test]
+async fn reports_mint_not_started() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/mint/v1"))
+        .respond_with(
+            ResponseTemplate::new(400).set_body_string(r#"{"statusCode":400,"error":"Bad Request","message":"Mint has not started yet"}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let err = client.mint_tokens(mint_request()).await.unwrap_err();
+
+    let MagicedenApiError::Mint(err) = err else { panic!("expected mint error, got {err:?}") };
+    assert_eq!(err.kind, MintErrorKind::NotStarted);
+    assert_eq!(err.message, "Mint has not started yet");
+}
+
+/// Response without executable items and the given errors
+fn response_without_steps(errors: serde_json::Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "steps": [{ "id": "sale", "action": "", "description": "", "kind": "transaction", "items": [] }],
+        "errors": errors
+    }))
+}
+
+#[tokio::test]
+async fn reports_sold_out_without_steps() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/mint/v1"))
+        .respond_with(response_without_steps(json!([
+            { "message": "Balance too low to proceed with transaction" },
+            { "message": "Mint is sold out", "orderId": "mint:0x8d04a8c79ceb0889bdd12acdf3fa9d207ed3ff63" },
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let err = client.mint_tokens(mint_request()).await.unwrap_err();
+
+    let MagicedenApiError::Mint(err) = err else { panic!("expected mint error, got {err:?}") };
+    assert_eq!(err.kind, MintErrorKind::SoldOut);
+    assert_eq!(err.message, "Mint is sold out");
+    // All errors are kept
+    assert_eq!(err.errors.len(), 2);
+    assert_eq!(err.errors[0].mint_error_kind(), None);
+}
+
+#[tokio::test]
+async fn returns_other_errors_unchanged() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/mint/v1"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .set_body_string(r#"{"statusCode":400,"error":"Bad Request","message":"Recommended gas price is too low"}"#),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/mint/v1"))
+        .respond_with(response_without_steps(json!([{ "message": "Balance too low to proceed with transaction" }])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let err = client.mint_tokens(mint_request()).await.unwrap_err();
+    assert!(matches!(err, MagicedenApiError::BadRequest(_)));
+
+    let res = client.mint_tokens(mint_request()).await.unwrap();
+    assert_eq!(res.errors.len(), 1);
+    assert_eq!(res.errors[0].mint_error_kind(), None);
+}
@@ -0,0 +1,71 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::{
+    api::{Depth, OrderDepthRequest, OrderDepthResponse, Side},
+    primitives::Decimal,
+};
+use serde_json::json;
+use std::str::FromStr;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_retrieve_pool_depth() {
+    let server = MockServer::start().await;
+    let body = fixture("response_depth.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/orders/depth/v1"))
+        .and(query_param("side", "sell"))
+        .and(query_param("collection", "0x5af0d9827e0c53e4799bb226655a1de152a425a5"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res =
+        client.retrieve_order_depth(OrderDepthRequest::collection(Side::Sell, "0x5af0d9827e0c53e4799bb226655a1de152a425a5")).await.unwrap();
+
+    let dec = |s| Decimal::from_str(s).unwrap();
+    assert_eq!(res.depth.len(), 4);
+    // Prices and fractional quantities are kept exactly
+    assert_eq!(res.depth[1].price, dec("0.01255"));
+    assert_eq!(res.depth[3].price, dec("0.013061224489795919"));
+    assert_eq!(res.depth[3].quantity, dec("0.75"));
+    assert_eq!(res.total_quantity(), dec("5.25"));
+
+    assert_eq!(res.fill_cost(&Side::Sell, dec("3")), Some(dec("0.03765")));
+    assert_eq!(res.fill_cost(&Side::Sell, dec("6")), None);
+    assert_eq!(res.fill_cost(&Side::Sell, dec("-1")), None);
+}
+
+#[test]
+fn fills_bids_from_the_highest_price() {
+    let dec = |s| Decimal::from_str(s).unwrap();
+    let res: OrderDepthResponse = serde_json::from_value(json!({
+        "depth": [
+            { "price": 0.011, "quantity": 2 },
+            { "price": 0.012, "quantity": 1 },
+            { "price": 0.0105, "quantity": 3 },
+        ]
+    }))
+    .unwrap();
+
+    // 1 at 0.012 and 1 at 0.011, regardless of the order returned by the API
+    assert_eq!(res.fill_cost(&Side::Buy, dec("2")), Some(dec("0.023")));
+    // The same levels as asks fill from the lowest price
+    assert_eq!(res.fill_cost(&Side::Sell, dec("2")), Some(dec("0.021")));
+    assert_eq!(res.fill_cost(&Side::Buy, Decimal::ZERO), Some(Decimal::ZERO));
+}
+
+#[test]
+fn rejects_overflowing_fill_cost() {
+    let res = OrderDepthResponse { depth: vec![Depth { price: Decimal::MAX, quantity: Decimal::from(2) }] };
+
+    assert_eq!(res.fill_cost(&Side::Sell, Decimal::from(2)), None);
+    assert_eq!(res.fill_cost(&Side::Sell, Decimal::ONE), Some(Decimal::MAX));
+}
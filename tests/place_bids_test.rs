@@ -0,0 +1,59 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{BidParams, BidRequest, BuyTokensKind, StepId};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_place_collection_bid() {
+    let server = MockServer::start().await;
+    let body = fixture("response_bid.json");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/bid/v5"))
+        .and(body_partial_json(json!({
+            "maker": "0xd8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "params": [
+                { "collection": "0x8132176f60e9db5288d84feeefae455c8caa3f95", "weiPrice": "100000000000000000", "quantity": 2 },
+                {
+                    "collection": "0x8132176f60e9db5288d84feeefae455c8caa3f95",
+                    "attributeKey": "Background",
+                    "attributeValue": "Blue",
+                    "weiPrice": "120000000000000000",
+                },
+            ],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = BidRequest {
+        maker: "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".parse().unwrap(),
+        params: vec![
+            BidParams {
+                quantity: Some(2),
+                ..BidParams::collection("0x8132176f60e9db5288d84feeefae455c8caa3f95", "100000000000000000".parse().unwrap())
+            },
+            BidParams::attribute("0x8132176f60e9db5288d84feeefae455c8caa3f95", "Background", "Blue", "120000000000000000".parse().unwrap()),
+        ],
+        ..Default::default()
+    };
+
+    let res = client.place_bids(req).await.unwrap();
+
+    let ids: Vec<_> = res.steps.iter().map(|step| step.id.clone()).collect();
+    assert_eq!(ids, vec![StepId::CurrencyWrapping, StepId::CurrencyApproval, StepId::OrderSignature]);
+
+    let wrap = res.step(&StepId::CurrencyWrapping).unwrap();
+    assert_eq!(wrap.kind, BuyTokensKind::Transaction);
+    assert!(wrap.items[0].data.as_ref().unwrap().as_transaction().is_some());
+
+    let sign = res.step(&StepId::OrderSignature).unwrap();
+    assert!(sign.items[0].data.as_ref().unwrap().as_signature().is_some());
+}
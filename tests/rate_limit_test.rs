@@ -0,0 +1,33 @@
+mod common;
+use common::fixture;
+
+use futures::future::join_all;
+use magiceden_client_rs::{types::api::AsksRequest, MagicedenApiConfig, MagicedenClient, RateLimit};
+use std::time::{Duration, Instant};
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn limits_requests_across_client_clones() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_asks.json")))
+        .expect(6)
+        .mount(&server)
+        .await;
+
+    let cfg = MagicedenApiConfig {
+        base_url: Some(server.uri()),
+        rate_limit: Some(RateLimit { requests_per_second: 10, burst: 2 }),
+        ..Default::default()
+    };
+    let client = MagicedenClient::new(cfg);
+    let clone = client.clone();
+
+    let start = Instant::now();
+    let requests = (0..3).flat_map(|_| [client.retrieve_asks(AsksRequest::default()), clone.retrieve_asks(AsksRequest::default())]);
+    let results = join_all(requests).await;
+
+    assert!(results.iter().all(|res| res.is_ok()));
+    // The burst of 2 is shared, the other 4 requests wait for 100ms each
+    assert!(start.elapsed() >= Duration::from_millis(350), "elapsed {:?}", start.elapsed());
+}
@@ -0,0 +1,117 @@
+mod common;
+use common::{fixture, mock_client};
+
+use futures::StreamExt;
+use magiceden_client_rs::{
+    types::api::{Activity, ActivityType, CollectionActivityRequest, UserActivityRequest},
+    StreamOptions,
+};
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_stream_collection_activity() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/collections/activity/v6"))
+        .and(query_param("collection", "0x8132176f60e9db5288d84feeefae455c8caa3f95"))
+        .and(query_param("types", "ask_cancel"))
+        .and(query_param_is_missing("continuation"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_activity.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/collections/activity/v6"))
+        .and(query_param("continuation", "MTcxNDAzOTUwMA=="))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_activity_last_page.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = CollectionActivityRequest {
+        collection: Some("0x8132176f60e9db5288d84feeefae455c8caa3f95".to_string()),
+        types: Some(vec![ActivityType::Sale, ActivityType::AskCancel, ActivityType::Mint]),
+        ..Default::default()
+    };
+
+    let activities: Vec<_> = client.collection_activity_stream(req).map(Result::unwrap).collect().await;
+
+    let types: Vec<_> = activities.iter().map(Activity::activity_type).collect();
+    assert_eq!(
+        types,
+        vec![
+            Some(ActivityType::Sale),
+            Some(ActivityType::Ask),
+            Some(ActivityType::Bid),
+            Some(ActivityType::AskCancel),
+            Some(ActivityType::BidCancel),
+            Some(ActivityType::Transfer),
+            Some(ActivityType::Mint),
+            None,
+        ]
+    );
+
+    let Activity::Sale(sale) = &activities[0] else { panic!("expected a sale") };
+    assert_eq!(sale.price.as_ref().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert_eq!(sale.fill_source.as_ref().unwrap().domain.as_deref(), Some("magiceden.io"));
+
+    let Activity::Bid(bid) = &activities[2] else { panic!("expected a bid") };
+    assert_eq!(bid.amount, Some(3));
+
+    // Unknown types are kept as raw value
+    assert_eq!(activities[7].timestamp(), Some(1644600000));
+}
+
+#[tokio::test]
+async fn can_retrieve_user_activity() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/users/activity/v6"))
+        .and(query_param("users", "0xd8da6bf26964af9d7eed9e03e53415d37aa96045"))
+        .and(query_param("types", "mint"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_activity_last_page.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = UserActivityRequest {
+        users: vec!["0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string()],
+        types: Some(vec![ActivityType::Mint]),
+        ..Default::default()
+    };
+
+    let res = client.retrieve_user_activity(req).await.unwrap();
+
+    let Activity::Mint(mint) = &res.activities[0] else { panic!("expected a mint") };
+    assert_eq!(mint.timestamp, 1644600664);
+    assert!(res.continuation.is_none());
+}
+
+#[tokio::test]
+async fn can_stream_user_activity_with_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/users/activity/v6"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_activity_last_page.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = UserActivityRequest { users: vec!["0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string()], ..Default::default() };
+    let options = StreamOptions { max_items: Some(1), page_size: Some(1) };
+
+    let activities: Vec<_> = client.user_activity_stream_with_options(req, options).collect().await;
+
+    assert_eq!(activities.len(), 1);
+    assert!(activities[0].is_ok());
+}
@@ -1,7 +1,29 @@
 mod common;
-use common::test_client;
+use common::{fixture, mock_client, test_client};
 
-use magiceden_client_rs::types::api::AsksRequest;
+use futures::StreamExt;
+use magiceden_client_rs::{types::api::AsksRequest, MagicedenApiConfig, MagicedenClient, RetryPolicy, StreamOptions};
+use serde_json::Value;
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Asks page of the fixture with the given continuation.
+fn asks_page(continuation: Option<&str>) -> Value {
+    let mut page: Value = serde_json::from_str(&fixture("response_asks.json")).unwrap();
+    page["continuation"] = continuation.into();
+    page
+}
+
+async fn mount_page(server: &MockServer, sent: Option<&str>, next: Option<&str>, times: u64) {
+    let mock = Mock::given(method("GET")).and(path("/v3/rtp/ethereum/orders/asks/v5"));
+    let mock = match sent {
+        Some(sent) => mock.and(query_param("continuation", sent)),
+        None => mock.and(query_param_is_missing("continuation")),
+    };
+    mock.respond_with(ResponseTemplate::new(200).set_body_json(asks_page(next))).expect(times).mount(server).await;
+}
 
 #[tokio::test]
 async fn can_retrieve_listing() {
@@ -14,3 +36,100 @@ async fn can_retrieve_listing() {
     assert_eq!(res.orders.len(), 1000);
     assert!(res.continuation.is_some());
 }
+
+// Test is optional because it pulls several pages from the live API
+#[ignore]
+#[tokio::test]
+async fn can_stream_asks() {
+    let client = test_client();
+
+    let req = AsksRequest::default();
+    let options = StreamOptions { max_items: Some(1500), page_size: Some(1000) };
+
+    let orders: Vec<_> = client.asks_stream_with_options(req, options).collect().await;
+
+    assert_eq!(orders.len(), 1500);
+    assert!(orders.iter().all(|o| o.is_ok()));
+}
+
+#[tokio::test]
+async fn streams_asks_following_continuation() {
+    let server = MockServer::start().await;
+    mount_page(&server, None, Some("page2"), 1).await;
+    mount_page(&server, Some("page2"), Some("page3"), 1).await;
+    mount_page(&server, Some("page3"), None, 1).await;
+
+    let client = mock_client(server.uri());
+
+    let orders: Vec<_> = client.asks_stream(AsksRequest::default()).collect().await;
+
+    assert_eq!(orders.len(), 3);
+    assert!(orders.iter().all(|o| o.is_ok()));
+}
+
+#[tokio::test]
+async fn stops_asks_stream_on_repeated_continuation() {
+    let server = MockServer::start().await;
+    mount_page(&server, None, Some("same"), 1).await;
+    mount_page(&server, Some("same"), Some("same"), 1).await;
+
+    let client = mock_client(server.uri());
+
+    let orders: Vec<_> = client.asks_stream(AsksRequest::default()).collect().await;
+
+    assert_eq!(orders.len(), 2);
+}
+
+#[tokio::test]
+async fn stops_asks_stream_at_max_items() {
+    let server = MockServer::start().await;
+    mount_page(&server, None, Some("page2"), 1).await;
+    mount_page(&server, Some("page2"), Some("page3"), 1).await;
+    mount_page(&server, Some("page3"), None, 0).await;
+
+    let client = mock_client(server.uri());
+
+    let options = StreamOptions { max_items: Some(2), page_size: Some(1) };
+    let orders: Vec<_> = client.asks_stream_with_options(AsksRequest::default(), options).collect().await;
+
+    assert_eq!(orders.len(), 2);
+}
+
+#[tokio::test]
+async fn can_retrieve_asks_from_mock_server() {
+    let server = MockServer::start().await;
+    let body = fixture("response_asks.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/orders/asks/v5"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.retrieve_asks(AsksRequest { limit: Some(1), ..Default::default() }).await.unwrap();
+
+    assert!(!res.orders.is_empty());
+}
+
+#[tokio::test]
+async fn retries_rate_limited_asks() {
+    let server = MockServer::start().await;
+    let body = fixture("response_asks.json");
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET")).respond_with(ResponseTemplate::new(200).set_body_string(body)).expect(1).mount(&server).await;
+
+    let cfg = MagicedenApiConfig { base_url: Some(server.uri()), retry: Some(RetryPolicy::default()), ..Default::default() };
+    let client = MagicedenClient::new(cfg);
+
+    let res = client.retrieve_asks(AsksRequest::default()).await.unwrap();
+
+    assert!(!res.orders.is_empty());
+}
@@ -0,0 +1,100 @@
+mod common;
+use common::{fixture, mock_client, test_client};
+
+use futures::StreamExt;
+use magiceden_client_rs::{
+    types::api::{BidsRequest, SortBy},
+    StreamOptions,
+};
+use serde_json::Value;
+use wiremock::{
+    matchers::{method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Bids page of the fixture with the given continuation.
+fn bids_page(continuation: Option<&str>) -> Value {
+    let mut page: Value = serde_json::from_str(&fixture("response_bids.json")).unwrap();
+    page["continuation"] = continuation.into();
+    page
+}
+
+async fn mount_page(server: &MockServer, sent: Option<&str>, next: Option<&str>) {
+    let mock = Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/orders/bids/v6"))
+        .and(query_param("maker", "0xf296178d553c8ec21a2fbd2c5dda8ca9ac905a00"))
+        .and(query_param("status", "active"))
+        .and(query_param("sortBy", "price"))
+        .and(query_param("excludeEOA", "true"))
+        .and(query_param("limit", "50"));
+    let mock = match sent {
+        Some(sent) => mock.and(query_param("continuation", sent)),
+        None => mock.and(query_param_is_missing("continuation")),
+    };
+    mock.respond_with(ResponseTemplate::new(200).set_body_json(bids_page(next))).expect(1).mount(server).await;
+}
+
+// Test is optional because it requires the live API
+#[ignore]
+#[tokio::test]
+async fn can_retrieve_bids() {
+    let client = test_client();
+
+    let req = BidsRequest { limit: Some(100), ..Default::default() };
+
+    let res = client.retrieve_bids(req).await.unwrap();
+
+    assert_eq!(res.orders.len(), 100);
+    assert!(res.continuation.is_some());
+}
+
+#[tokio::test]
+async fn can_stream_attribute_bids_from_mock_server() {
+    let server = MockServer::start().await;
+    let body = fixture("response_bids.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/orders/bids/v6"))
+        .and(query_param("collection", "0x8132176f60e9db5288d84feeefae455c8caa3f95"))
+        .and(query_param("attribute[Background]", "Light Blue"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = BidsRequest {
+        collection: Some("0x8132176f60e9db5288d84feeefae455c8caa3f95".to_string()),
+        attributes: vec![("Background".to_string(), "Light Blue".to_string())],
+        ..Default::default()
+    };
+
+    let res = client.retrieve_bids(req.clone()).await.unwrap();
+    assert_eq!(res.orders.len(), 1);
+
+    // The mock returns the same continuation for every page, so the stream stops after the second page
+    let orders: Vec<_> = client.bids_stream_with_options(req, StreamOptions { max_items: Some(10), page_size: None }).collect().await;
+    assert_eq!(orders.len(), 2);
+}
+
+#[tokio::test]
+async fn streams_bids_following_continuation() {
+    let server = MockServer::start().await;
+    mount_page(&server, None, Some("page2")).await;
+    mount_page(&server, Some("page2"), None).await;
+
+    let client = mock_client(server.uri());
+
+    let req = BidsRequest {
+        maker: Some("0xf296178d553c8ec21a2fbd2c5dda8ca9ac905a00".to_string()),
+        status: Some("active".to_string()),
+        sort_by: Some(SortBy::Price),
+        exclude_eoa: Some(true),
+        ..Default::default()
+    };
+    let options = StreamOptions { max_items: None, page_size: Some(50) };
+    let orders: Vec<_> = client.bids_stream_with_options(req, options).collect().await;
+
+    assert_eq!(orders.len(), 2);
+    assert!(orders.iter().all(|o| o.is_ok()));
+}
@@ -0,0 +1,52 @@
+mod common;
+use common::{fixture, mock_client};
+
+use futures::StreamExt;
+use magiceden_client_rs::types::api::{CollectionsRequest, CollectionsSortBy};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_retrieve_collections() {
+    let server = MockServer::start().await;
+    let body = fixture("response_collections.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/collections/v7"))
+        .and(query_param("community", "skulls"))
+        .and(query_param("sortBy", "7DayVolume"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = CollectionsRequest {
+        community: Some("skulls".to_string()),
+        sort_by: Some(CollectionsSortBy::SevenDayVolume),
+        include_top_bid: Some(true),
+        ..Default::default()
+    };
+
+    let res = client.retrieve_collections(req.clone()).await.unwrap();
+    assert_eq!(res.collections.len(), 2);
+
+    let collection = &res.collections[0];
+    assert_eq!(collection.slug.as_deref(), Some("skulls"));
+    // tokenCount is returned as string, ownerCount as number
+    assert_eq!(collection.token_count, Some(10000));
+    assert_eq!(collection.owner_count, Some(4123));
+    assert_eq!(res.collections[1].token_count, Some(9999));
+    assert_eq!(collection.floor_ask.as_ref().unwrap().price.as_ref().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert_eq!(collection.floor_ask.as_ref().unwrap().price.as_ref().unwrap().amount.decimal, 0.049);
+    assert_eq!(collection.volume.as_ref().unwrap().seven_day, Some(9.87));
+    assert_eq!(collection.rank.as_ref().unwrap().thirty_day, None);
+    assert_eq!(collection.royalties.as_ref().unwrap().bps, Some(500));
+    assert_eq!(collection.marketplace_fees.as_ref().unwrap()[0].bps, 200);
+
+    // Without continuation the stream ends after the first page
+    let collections: Vec<_> = client.collections_stream(req).collect().await;
+    assert_eq!(collections.len(), 2);
+}
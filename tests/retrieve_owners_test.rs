@@ -0,0 +1,123 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::{api::OwnersRequest, MagicedenApiError};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const COLLECTION: &str = "0x8132176f60e9db5288d84feeefae455c8caa3f95";
+
+#[tokio::test]
+async fn can_retrieve_attribute_owners() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/owners/v2"))
+        .and(query_param("collection", COLLECTION))
+        .and(query_param("attributes[Background]", "Blue"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_owners.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = OwnersRequest {
+        collection: Some(COLLECTION.to_string()),
+        attributes: vec![("Background".to_string(), "Blue".to_string())],
+        ..Default::default()
+    };
+
+    let res = client.retrieve_owners(req).await.unwrap();
+
+    assert_eq!(res.owners.len(), 3);
+    let whale = &res.owners[0].ownership;
+    assert_eq!(whale.token_count, Some(250));
+    assert_eq!(whale.on_sale_count, Some(12));
+    assert_eq!(whale.floor_ask_price.as_ref().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert!(res.owners[2].ownership.top_bid_value.is_none());
+}
+
+#[tokio::test]
+async fn computes_holder_concentration() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/collections/v7"))
+        .and(query_param("id", COLLECTION))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_collections.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/owners/v2"))
+        .and(query_param("collection", COLLECTION))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_owners.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let concentration = client.holder_concentration(COLLECTION, 2).await.unwrap();
+
+    // 10000 tokens held by 4123 owners, the top 2 hold 250 + 120
+    assert_eq!(concentration.top_n_tokens, 370);
+    assert!((concentration.top_n_share - 0.037).abs() < 1e-9);
+    assert!((concentration.unique_owner_ratio - 0.4123).abs() < 1e-9);
+}
+
+/// Owners page with `count` owners of 10 tokens each
+fn owners_page(count: usize) -> Value {
+    let page: Value = serde_json::from_str(&fixture("response_owners.json")).unwrap();
+    let mut owner = page["owners"][0].clone();
+    owner["ownership"]["tokenCount"] = json!("10");
+    json!({ "owners": vec![owner; count] })
+}
+
+#[tokio::test]
+async fn pages_owners_beyond_one_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/collections/v7"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_collections.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/owners/v2"))
+        .and(query_param("offset", "0"))
+        .and(query_param("limit", "500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(owners_page(500)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/owners/v2"))
+        .and(query_param("offset", "500"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(owners_page(1)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let concentration = client.holder_concentration(COLLECTION, 501).await.unwrap();
+
+    // 501 holders of 10 tokens each out of 10000 tokens
+    assert_eq!(concentration.top_n, 501);
+    assert_eq!(concentration.top_n_tokens, 5010);
+    assert!((concentration.top_n_share - 0.501).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn rejects_invalid_top_n() {
+    let client = mock_client("http://localhost:1".to_string());
+
+    assert!(matches!(client.holder_concentration(COLLECTION, 0).await, Err(MagicedenApiError::InvalidArgument(_))));
+    assert!(matches!(client.holder_concentration(COLLECTION, 10_001).await, Err(MagicedenApiError::InvalidArgument(_))));
+    assert!(matches!(client.holder_concentration(COLLECTION, u16::MAX).await, Err(MagicedenApiError::InvalidArgument(_))));
+}
@@ -0,0 +1,54 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{Kind, OrderSide, SalesRequest};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_retrieve_sales() {
+    let server = MockServer::start().await;
+    let body = fixture("response_sales.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/sales/v6"))
+        .and(query_param("collection", "0x8132176f60e9db5288d84feeefae455c8caa3f95"))
+        .and(query_param("attributes[Background]", "Blue"))
+        .and(query_param("startTimestamp", "1714000000"))
+        .and(query_param("orderSide", "ask"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = SalesRequest {
+        collection: Some("0x8132176f60e9db5288d84feeefae455c8caa3f95".to_string()),
+        attributes: vec![("Background".to_string(), "Blue".to_string())],
+        start_timestamp: Some(1714000000),
+        order_side: Some(OrderSide::Ask),
+        ..Default::default()
+    };
+
+    let res = client.retrieve_sales(req).await.unwrap();
+
+    assert_eq!(res.sales.len(), 3);
+
+    let ask = &res.sales[0];
+    assert!(matches!(ask.order_kind, Some(Kind::PaymentProcessorV2)));
+    assert_eq!(ask.maker(), Some(&ask.from));
+    assert_eq!(ask.amount, Some(1));
+    assert_eq!(ask.price.as_ref().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert_eq!(ask.royalty_fee_breakdown.as_ref().unwrap()[0].raw_amount.as_ref().unwrap().to_string(), "2450000000000000");
+    assert_eq!(ask.block, Some(19730001));
+
+    let bid = &res.sales[1];
+    assert_eq!(bid.maker(), Some(&bid.to));
+    assert_eq!(bid.taker(), Some(&bid.from));
+
+    // Unknown order kinds do not break deserialization
+    assert!(matches!(res.sales[2].order_kind, Some(Kind::Unknown)));
+    assert_eq!(res.sales[2].wash_trading_score, Some(1.0));
+}
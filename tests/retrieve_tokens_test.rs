@@ -0,0 +1,53 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{TokensRequest, TokensSortBy};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_retrieve_tokens_by_attribute() {
+    let server = MockServer::start().await;
+    let body = fixture("response_tokens.json");
+    Mock::given(method("GET"))
+        .and(path("/v3/rtp/ethereum/tokens/v7"))
+        .and(query_param("collection", "0x8132176f60e9db5288d84feeefae455c8caa3f95"))
+        .and(query_param("attributes[Background]", "Blue"))
+        .and(query_param("maxRarityRank", "100"))
+        .and(query_param("maxFloorAskPrice", "0.06"))
+        .and(query_param("sortBy", "rarity"))
+        .and(query_param("includeTopBid", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = TokensRequest {
+        collection: Some("0x8132176f60e9db5288d84feeefae455c8caa3f95".to_string()),
+        attributes: vec![("Background".to_string(), "Blue".to_string())],
+        max_rarity_rank: Some(100),
+        max_floor_ask_price: Some(0.06),
+        sort_by: Some(TokensSortBy::Rarity),
+        include_top_bid: Some(true),
+        include_last_sale: Some(true),
+        ..Default::default()
+    };
+
+    let res = client.retrieve_tokens(req).await.unwrap();
+
+    assert_eq!(res.tokens.len(), 2);
+    assert!(res.continuation.is_some());
+
+    let token = &res.tokens[0];
+    assert_eq!(token.token.token_id, "653");
+    assert_eq!(token.token.rarity_rank, Some(12));
+    assert_eq!(token.floor_ask_price().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert_eq!(token.floor_ask_price().unwrap().amount.decimal, 0.049);
+    assert_eq!(token.top_bid_price().unwrap().currency.symbol, "WETH");
+    assert!(token.token.last_sale.as_ref().unwrap().price.is_some());
+    assert_eq!(token.token.attributes.as_ref().unwrap()[0].value, "Blue");
+}
@@ -0,0 +1,43 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::UserTokensRequest;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_retrieve_user_tokens() {
+    let server = MockServer::start().await;
+    let user = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045";
+    let body = fixture("response_user_tokens.json");
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/rtp/ethereum/users/{user}/tokens/v10")))
+        .and(query_param("community", "skulls"))
+        .and(query_param("includeTopBid", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = UserTokensRequest { community: Some("skulls".to_string()), include_top_bid: Some(true), ..Default::default() };
+
+    let res = client.retrieve_user_tokens(user, req).await.unwrap();
+
+    assert_eq!(res.tokens.len(), 2);
+
+    let listed = &res.tokens[0];
+    assert_eq!(listed.ownership.token_count, Some(1));
+    assert!(listed.ownership.acquired_at.is_some());
+    assert_eq!(listed.ownership.floor_ask.as_ref().unwrap().price.as_ref().unwrap().amount.raw.to_string(), "55000000000000000");
+    assert_eq!(listed.collection_floor_price().unwrap().amount.raw.to_string(), "49000000000000000");
+    assert_eq!(listed.top_bid_price().unwrap().currency.symbol, "WETH");
+    assert_eq!(listed.token.rarity, Some(412.77));
+
+    let erc1155 = &res.tokens[1];
+    assert_eq!(erc1155.ownership.token_count, Some(3));
+    assert!(erc1155.top_bid_price().is_none());
+}
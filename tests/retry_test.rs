@@ -0,0 +1,87 @@
+mod common;
+use common::fixture;
+
+use magiceden_client_rs::{
+    types::{
+        api::{AsksRequest, BuyTokensRequest},
+        MagicedenApiError,
+    },
+    MagicedenApiConfig, MagicedenClient, RetryPolicy,
+};
+use std::time::{Duration, Instant};
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+fn retry_client(base_url: String, policy: RetryPolicy) -> MagicedenClient {
+    MagicedenClient::new(MagicedenApiConfig { base_url: Some(base_url), retry: Some(policy), ..Default::default() })
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy { initial_backoff: Duration::from_millis(1), jitter: false, ..Default::default() }
+}
+
+#[tokio::test]
+async fn retries_unavailable_and_honors_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture("response_asks.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = retry_client(server.uri(), fast_policy());
+
+    let start = Instant::now();
+    let res = client.retrieve_asks(AsksRequest::default()).await.unwrap();
+
+    assert!(!res.orders.is_empty());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn does_not_wait_for_retry_after_beyond_max_backoff() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "86400"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = retry_client(server.uri(), fast_policy());
+
+    let start = Instant::now();
+    let err = client.retrieve_asks(AsksRequest::default()).await.unwrap_err();
+
+    let MagicedenApiError::RateLimited(res) = err else { panic!("expected rate limit, got {err:?}") };
+    assert_eq!(res.retry_after, Some(Duration::from_secs(86400)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET")).respond_with(ResponseTemplate::new(503)).expect(2).mount(&server).await;
+
+    let client = retry_client(server.uri(), RetryPolicy { max_attempts: 2, ..fast_policy() });
+
+    let err = client.retrieve_asks(AsksRequest::default()).await.unwrap_err();
+
+    assert!(matches!(err, MagicedenApiError::ServerError(_)));
+}
+
+#[tokio::test]
+async fn does_not_retry_non_idempotent_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST")).respond_with(ResponseTemplate::new(503)).expect(1).mount(&server).await;
+
+    let client = retry_client(server.uri(), fast_policy());
+
+    let err = client.buy_tokens(BuyTokensRequest::default()).await.unwrap_err();
+
+    assert_eq!(err.status_code(), Some(503));
+}
@@ -0,0 +1,84 @@
+mod common;
+use common::{fixture, mock_client};
+
+use magiceden_client_rs::types::api::{Bid, SellTokensRequest};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn can_sell_tokens() {
+    let server = MockServer::start().await;
+    let body = fixture("response_sell.json");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/sell/v7"))
+        .and(body_partial_json(json!({
+            "items": [{ "token": "0x8132176f60e9db5288d84feeefae455c8caa3f95:653", "quantity": 1 }],
+            "taker": "0xd8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "normalizeRoyalties": false,
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let req = SellTokensRequest {
+        items: vec![Bid { token: "0x8132176f60e9db5288d84feeefae455c8caa3f95:653".to_string(), quantity: Some(1), ..Default::default() }],
+        taker: "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".parse().unwrap(),
+        normalize_royalties: Some(false),
+        ..Default::default()
+    };
+
+    let res = client.sell_tokens(req).await.unwrap();
+
+    assert_eq!(res.path.len(), 1);
+    assert_eq!(res.path[0].token_id, "653");
+    assert_eq!(res.steps.len(), 3);
+    assert!(res.errors.is_empty());
+}
+
+#[tokio::test]
+async fn can_sell_tokens_without_errors_and_path() {
+    let server = MockServer::start().await;
+    let mut body: Value = serde_json::from_str(&fixture("response_sell.json")).unwrap();
+    body.as_object_mut().unwrap().remove("errors");
+    body.as_object_mut().unwrap().remove("path");
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/sell/v7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.sell_tokens(SellTokensRequest::default()).await.unwrap();
+
+    assert!(res.errors.is_empty());
+    assert!(res.path.is_empty());
+    assert_eq!(res.steps.len(), 3);
+}
+
+#[tokio::test]
+async fn can_sell_tokens_with_error_without_order_id() {
+    let server = MockServer::start().await;
+    let mut body: Value = serde_json::from_str(&fixture("response_sell.json")).unwrap();
+    body["errors"] = json!([{ "message": "Insufficient balance" }]);
+    Mock::given(method("POST"))
+        .and(path("/v3/rtp/ethereum/execute/sell/v7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(server.uri());
+
+    let res = client.sell_tokens(SellTokensRequest::default()).await.unwrap();
+
+    assert_eq!(res.errors[0].message, "Insufficient balance");
+    assert!(res.errors[0].order_id.is_none());
+}